@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use ide::InlayHintsConfig;
+
 pub const CONFIG_KEY: &str = "nil";
 
 #[derive(Debug, Clone)]
@@ -7,6 +9,11 @@ pub struct Config {
     pub root_path: PathBuf,
 
     pub gleam_binary: PathBuf,
+
+    /// Whether workspace symbol search also looks into dependency packages.
+    pub workspace_symbols_include_dependencies: bool,
+
+    pub inlay_hints: InlayHintsConfig,
 }
 
 impl Config {
@@ -15,6 +22,8 @@ impl Config {
         Self {
             root_path,
             gleam_binary: "gleam".into(),
+            workspace_symbols_include_dependencies: false,
+            inlay_hints: InlayHintsConfig::default(),
         }
     }
 
@@ -33,6 +42,37 @@ impl Config {
             }
         }
 
+        if let Some(v) = value.pointer_mut("/workspaceSymbols/includeDependencies") {
+            match serde_json::from_value::<bool>(v.take()) {
+                Ok(include) => {
+                    self.workspace_symbols_include_dependencies = include;
+                }
+                Err(e) => {
+                    errors.push(format!(
+                        "Invalid value of `workspaceSymbols.includeDependencies`: {e}"
+                    ));
+                }
+            }
+        }
+
+        let inlay_hints = [
+            ("bindingTypes", &mut self.inlay_hints.binding_types),
+            (
+                "lambdaParameterTypes",
+                &mut self.inlay_hints.lambda_param_types,
+            ),
+            ("pipeSteps", &mut self.inlay_hints.pipe_steps),
+            ("parameterNames", &mut self.inlay_hints.parameter_names),
+        ];
+        for (key, enabled) in inlay_hints {
+            if let Some(v) = value.pointer_mut(&format!("/inlayHints/{key}")) {
+                match serde_json::from_value::<bool>(v.take()) {
+                    Ok(v) => *enabled = v,
+                    Err(e) => errors.push(format!("Invalid value of `inlayHints.{key}`: {e}")),
+                }
+            }
+        }
+
         errors
     }
 }
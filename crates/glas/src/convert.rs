@@ -1,9 +1,10 @@
 use crate::{semantic_tokens, LineMap, Result, Vfs};
 use async_lsp::{ErrorCode, ResponseError};
 use ide::{
-    CompletionItem, CompletionItemKind, CompletionRelevance, Diagnostic, DiagnosticKind, FileId,
-    FilePos, FileRange, HlRange, HlRelated, HoverResult, Severity, SignatureHelp, TextEdit,
-    WorkspaceEdit,
+    Assist, AssistKind, CallHierarchyItem, CompletionItem, CompletionItemKind, CompletionRelevance,
+    Diagnostic, DiagnosticKind, DocumentSymbol, FileId, FilePos, FileRange, Fold, FoldKind,
+    HlRange, HlRelated, HoverResult, InlayHint, InlayHintKind, Severity, SignatureHelp, SymbolKind,
+    TextEdit, WorkspaceEdit, WorkspaceSymbol,
 };
 use lsp::{
     DiagnosticTag, DocumentHighlight, DocumentHighlightKind, Documentation, Hover, MarkupContent,
@@ -35,6 +36,17 @@ pub(crate) fn from_file_pos(
     Ok((FilePos::new(file, pos), line_map))
 }
 
+/// The function a call hierarchy item stands for, identified by the start of its name.
+pub(crate) fn from_call_hierarchy_item(
+    vfs: &Vfs,
+    item: &lsp::CallHierarchyItem,
+) -> Result<(FilePos, Arc<LineMap>)> {
+    let file = vfs.file_for_uri(&item.uri)?;
+    let line_map = vfs.line_map_for_file(file);
+    let pos = from_pos(&line_map, item.selection_range.start)?;
+    Ok((FilePos::new(file, pos), line_map))
+}
+
 pub(crate) fn from_range(
     vfs: &Vfs,
     file: FileId,
@@ -121,6 +133,9 @@ pub(crate) fn to_completion_item(
             detail: None,
             description: item.signature,
         }),
+        tags: item
+            .is_deprecated
+            .then(|| vec![lsp::CompletionItemTag::DEPRECATED]),
         ..lsp::CompletionItem::default()
     };
 
@@ -129,6 +144,133 @@ pub(crate) fn to_completion_item(
     completion_item
 }
 
+pub(crate) fn to_inlay_hint(line_map: &LineMap, hint: InlayHint) -> lsp::InlayHint {
+    let (line, col) = line_map.line_col_for_pos(hint.pos);
+    let (kind, padding_left, padding_right) = match hint.kind {
+        InlayHintKind::Type => (lsp::InlayHintKind::TYPE, false, false),
+        InlayHintKind::PipeStep => (lsp::InlayHintKind::TYPE, true, false),
+        InlayHintKind::Parameter => (lsp::InlayHintKind::PARAMETER, false, true),
+    };
+    lsp::InlayHint {
+        position: Position::new(line, col),
+        label: lsp::InlayHintLabel::String(hint.label),
+        kind: Some(kind),
+        text_edits: None,
+        tooltip: None,
+        padding_left: Some(padding_left),
+        padding_right: Some(padding_right),
+        data: None,
+    }
+}
+
+pub(crate) fn to_call_hierarchy_item(vfs: &Vfs, item: CallHierarchyItem) -> lsp::CallHierarchyItem {
+    let line_map = vfs.line_map_for_file(item.nav.file_id);
+    lsp::CallHierarchyItem {
+        name: item.name.into(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: item.is_deprecated.then(|| vec![lsp::SymbolTag::DEPRECATED]),
+        detail: Some(item.module.into()),
+        uri: vfs.uri_for_file(item.nav.file_id),
+        range: to_range(&line_map, item.nav.full_range),
+        selection_range: to_range(&line_map, item.nav.focus_range),
+        data: None,
+    }
+}
+
+pub(crate) fn to_folding_range(line_map: &LineMap, fold: Fold) -> lsp::FoldingRange {
+    let (start_line, start_col) = line_map.line_col_for_pos(fold.range.start());
+    let (end_line, end_col) = line_map.line_col_for_pos(fold.range.end());
+    let kind = match fold.kind {
+        FoldKind::Comment => lsp::FoldingRangeKind::Comment,
+        FoldKind::Imports => lsp::FoldingRangeKind::Imports,
+        FoldKind::Block
+        | FoldKind::Case
+        | FoldKind::Clause
+        | FoldKind::List
+        | FoldKind::Tuple
+        | FoldKind::Adt => lsp::FoldingRangeKind::Region,
+    };
+    lsp::FoldingRange {
+        start_line,
+        start_character: Some(start_col),
+        end_line,
+        end_character: Some(end_col),
+        kind: Some(kind),
+        collapsed_text: None,
+    }
+}
+
+/// Chains the ranges, innermost first, through their parents.
+pub(crate) fn to_selection_range(
+    line_map: &LineMap,
+    ranges: Vec<TextRange>,
+) -> lsp::SelectionRange {
+    let mut ranges = ranges.into_iter().rev();
+    let mut selection = lsp::SelectionRange {
+        range: ranges
+            .next()
+            .map_or_else(Range::default, |range| to_range(line_map, range)),
+        parent: None,
+    };
+    for range in ranges {
+        selection = lsp::SelectionRange {
+            range: to_range(line_map, range),
+            parent: Some(Box::new(selection)),
+        };
+    }
+    selection
+}
+
+fn to_symbol_kind(kind: SymbolKind) -> lsp::SymbolKind {
+    match kind {
+        SymbolKind::Function => lsp::SymbolKind::FUNCTION,
+        SymbolKind::Adt => lsp::SymbolKind::CLASS,
+        SymbolKind::Variant => lsp::SymbolKind::ENUM_MEMBER,
+        SymbolKind::Field => lsp::SymbolKind::FIELD,
+        SymbolKind::TypeAlias => lsp::SymbolKind::TYPE_PARAMETER,
+        SymbolKind::Constant => lsp::SymbolKind::CONSTANT,
+        SymbolKind::Module => lsp::SymbolKind::MODULE,
+    }
+}
+
+pub(crate) fn to_document_symbol(
+    line_map: &LineMap,
+    symbol: DocumentSymbol,
+) -> lsp::DocumentSymbol {
+    let children = symbol
+        .children
+        .into_iter()
+        .map(|child| to_document_symbol(line_map, child))
+        .collect::<Vec<_>>();
+    #[allow(deprecated)]
+    lsp::DocumentSymbol {
+        name: symbol.name.into(),
+        detail: None,
+        kind: to_symbol_kind(symbol.kind),
+        tags: symbol
+            .is_deprecated
+            .then(|| vec![lsp::SymbolTag::DEPRECATED]),
+        deprecated: None,
+        range: to_range(line_map, symbol.full_range),
+        selection_range: to_range(line_map, symbol.focus_range),
+        children: (!children.is_empty()).then_some(children),
+    }
+}
+
+pub(crate) fn to_workspace_symbol(vfs: &Vfs, symbol: WorkspaceSymbol) -> lsp::WorkspaceSymbol {
+    let frange = FileRange::new(symbol.nav.file_id, symbol.nav.focus_range);
+    lsp::WorkspaceSymbol {
+        name: symbol.name.into(),
+        kind: to_symbol_kind(symbol.kind),
+        tags: symbol
+            .is_deprecated
+            .then(|| vec![lsp::SymbolTag::DEPRECATED]),
+        container_name: Some(symbol.container.into()),
+        location: lsp::OneOf::Left(to_location(vfs, frange)),
+        data: None,
+    }
+}
+
 pub(crate) fn to_diagnostics(
     uri: &Url,
     file: FileId,
@@ -161,9 +303,15 @@ pub(crate) fn to_diagnostics(
             },
             tags: {
                 let mut tags = Vec::new();
-                if diag.kind == DiagnosticKind::InactiveTarget {
+                if matches!(
+                    diag.kind,
+                    DiagnosticKind::InactiveTarget | DiagnosticKind::UnreachableClause
+                ) {
                     tags.push(DiagnosticTag::UNNECESSARY);
                 }
+                if matches!(diag.kind, DiagnosticKind::Deprecated { .. }) {
+                    tags.push(DiagnosticTag::DEPRECATED);
+                }
                 Some(tags)
             },
             data: None,
@@ -253,6 +401,19 @@ pub(crate) fn to_workspace_edit(vfs: &Vfs, ws_edit: WorkspaceEdit) -> lsp::Works
     }
 }
 
+pub(crate) fn to_code_action(vfs: &Vfs, assist: Assist) -> lsp::CodeAction {
+    let kind = match assist.kind {
+        AssistKind::QuickFix => lsp::CodeActionKind::QUICKFIX,
+        AssistKind::RefactorRewrite => lsp::CodeActionKind::REFACTOR_REWRITE,
+    };
+    lsp::CodeAction {
+        title: assist.label,
+        kind: Some(kind),
+        edit: Some(to_workspace_edit(vfs, assist.edits)),
+        ..Default::default()
+    }
+}
+
 pub(crate) fn to_text_edit(line_map: &LineMap, edit: TextEdit) -> lsp::TextEdit {
     lsp::TextEdit {
         range: to_range(line_map, edit.delete),
@@ -1,12 +1,18 @@
-use crate::{convert, lsp_ext::SyntaxTreeParams, StateSnapshot};
+use crate::{convert, lsp_ext, lsp_ext::SyntaxTreeParams, StateSnapshot};
 use anyhow::Result;
 use ide::{FileRange, GotoDefinitionResult};
+use lsp_types::request::{GotoTypeDefinitionParams, GotoTypeDefinitionResponse};
 use lsp_types::{
-    CompletionParams, CompletionResponse, Diagnostic, DocumentHighlight, DocumentHighlightParams,
-    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams, Location,
-    PrepareRenameResponse, ReferenceParams, RenameParams, SemanticTokens, SemanticTokensParams,
-    SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult, SignatureHelp,
-    SignatureHelpParams, TextDocumentPositionParams, Url, WorkspaceEdit,
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
+    CodeActionOrCommand, CodeActionParams, CodeActionResponse, CompletionParams,
+    CompletionResponse, Diagnostic, DocumentHighlight, DocumentHighlightParams,
+    DocumentSymbolParams, DocumentSymbolResponse, FoldingRange, FoldingRangeParams,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams, InlayHint, InlayHintParams,
+    Location, PrepareRenameResponse, Range, ReferenceParams, RenameParams, SelectionRange,
+    SelectionRangeParams, SemanticTokens, SemanticTokensParams, SemanticTokensRangeParams,
+    SemanticTokensRangeResult, SemanticTokensResult, SignatureHelp, SignatureHelpParams,
+    TextDocumentPositionParams, Url, WorkspaceEdit, WorkspaceSymbolParams, WorkspaceSymbolResponse,
 };
 
 const MAX_DIAGNOSTICS_CNT: usize = 128;
@@ -71,6 +77,37 @@ pub(crate) fn goto_definition(
     Ok(Some(GotoDefinitionResponse::Array(targets)))
 }
 
+pub(crate) fn goto_type_definition(
+    snap: StateSnapshot,
+    params: GotoTypeDefinitionParams,
+) -> Result<Option<GotoTypeDefinitionResponse>> {
+    let (fpos, _) = convert::from_file_pos(&snap.vfs(), &params.text_document_position_params)?;
+    let Some(ret) = snap.analysis.goto_type_definition(fpos)? else {
+        return Ok(None);
+    };
+    let vfs = snap.vfs();
+    let mut targets = ret
+        .targets
+        .into_iter()
+        .map(|target| {
+            convert::to_location(&vfs, FileRange::new(target.file_id, target.focus_range))
+        })
+        .collect::<Vec<_>>();
+    // Native files aren't loaded, so check the disk for them.
+    targets.extend(
+        ret.external_files
+            .iter()
+            .filter_map(|path| path.as_path())
+            .filter(|path| path.is_file())
+            .filter_map(|path| Url::from_file_path(path).ok())
+            .map(|uri| Location::new(uri, Range::default())),
+    );
+    if targets.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(GotoTypeDefinitionResponse::Array(targets)))
+}
+
 pub(crate) fn document_highlight(
     snap: StateSnapshot,
     params: DocumentHighlightParams,
@@ -121,6 +158,116 @@ pub(crate) fn rename(snap: StateSnapshot, params: RenameParams) -> Result<Option
     Ok(Some(resp))
 }
 
+pub(crate) fn prepare_call_hierarchy(
+    snap: StateSnapshot,
+    params: CallHierarchyPrepareParams,
+) -> Result<Option<Vec<CallHierarchyItem>>> {
+    let (fpos, _) = convert::from_file_pos(&snap.vfs(), &params.text_document_position_params)?;
+    let Some(item) = snap.analysis.prepare_call_hierarchy(fpos)? else {
+        return Ok(None);
+    };
+    Ok(Some(vec![convert::to_call_hierarchy_item(
+        &snap.vfs(),
+        item,
+    )]))
+}
+
+pub(crate) fn incoming_calls(
+    snap: StateSnapshot,
+    params: CallHierarchyIncomingCallsParams,
+) -> Result<Option<Vec<CallHierarchyIncomingCall>>> {
+    let (fpos, _) = convert::from_call_hierarchy_item(&snap.vfs(), &params.item)?;
+    let Some(calls) = snap.analysis.incoming_calls(fpos)? else {
+        return Ok(None);
+    };
+    let vfs = snap.vfs();
+    let calls = calls
+        .into_iter()
+        .map(|call| {
+            // The ranges are in the caller's file.
+            let line_map = vfs.line_map_for_file(call.from.nav.file_id);
+            CallHierarchyIncomingCall {
+                from_ranges: call
+                    .ranges
+                    .into_iter()
+                    .map(|range| convert::to_range(&line_map, range))
+                    .collect(),
+                from: convert::to_call_hierarchy_item(&vfs, call.from),
+            }
+        })
+        .collect();
+    Ok(Some(calls))
+}
+
+pub(crate) fn outgoing_calls(
+    snap: StateSnapshot,
+    params: CallHierarchyOutgoingCallsParams,
+) -> Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+    let (fpos, line_map) = convert::from_call_hierarchy_item(&snap.vfs(), &params.item)?;
+    let Some(calls) = snap.analysis.outgoing_calls(fpos)? else {
+        return Ok(None);
+    };
+    let vfs = snap.vfs();
+    let calls = calls
+        .into_iter()
+        .map(|call| CallHierarchyOutgoingCall {
+            to: convert::to_call_hierarchy_item(&vfs, call.to),
+            from_ranges: call
+                .ranges
+                .into_iter()
+                .map(|range| convert::to_range(&line_map, range))
+                .collect(),
+        })
+        .collect();
+    Ok(Some(calls))
+}
+
+pub(crate) fn folding_range(
+    snap: StateSnapshot,
+    params: FoldingRangeParams,
+) -> Result<Option<Vec<FoldingRange>>> {
+    let (file, line_map) = convert::from_file(&snap.vfs(), &params.text_document)?;
+    let folds = snap.analysis.folding_ranges(file)?;
+    let folds = folds
+        .into_iter()
+        .map(|fold| convert::to_folding_range(&line_map, fold))
+        .collect();
+    Ok(Some(folds))
+}
+
+pub(crate) fn selection_range(
+    snap: StateSnapshot,
+    params: SelectionRangeParams,
+) -> Result<Option<Vec<SelectionRange>>> {
+    let (file, line_map) = convert::from_file(&snap.vfs(), &params.text_document)?;
+    let positions = params
+        .positions
+        .into_iter()
+        .map(|pos| convert::from_pos(&line_map, pos))
+        .collect::<Result<Vec<_>>>()?;
+    let ranges = snap.analysis.selection_ranges(file, positions)?;
+    let ranges = ranges
+        .into_iter()
+        .map(|ranges| convert::to_selection_range(&line_map, ranges))
+        .collect();
+    Ok(Some(ranges))
+}
+
+pub(crate) fn code_action(
+    snap: StateSnapshot,
+    params: CodeActionParams,
+) -> Result<Option<CodeActionResponse>> {
+    let vfs = snap.vfs();
+    let (file, _) = convert::from_file(&vfs, &params.text_document)?;
+    let (_, range) = convert::from_range(&vfs, file, params.range)?;
+    let assists = snap.analysis.code_actions(FileRange::new(file, range))?;
+    let actions = assists
+        .into_iter()
+        .map(|assist| CodeActionOrCommand::CodeAction(convert::to_code_action(&vfs, assist)))
+        .collect();
+    Ok(Some(actions))
+}
+
 pub(crate) fn signature_help(
     snap: StateSnapshot,
     params: SignatureHelpParams,
@@ -134,12 +281,73 @@ pub(crate) fn signature_help(
     Ok(Some(resp))
 }
 
+pub(crate) fn inlay_hint(
+    snap: StateSnapshot,
+    params: InlayHintParams,
+) -> Result<Option<Vec<InlayHint>>> {
+    let (file, range, line_map) = {
+        let vfs = snap.vfs();
+        let (file, line_map) = convert::from_file(&vfs, &params.text_document)?;
+        let (_, range) = convert::from_range(&vfs, file, params.range)?;
+        (file, range, line_map)
+    };
+    let hints = snap
+        .analysis
+        .inlay_hints(file, Some(range), snap.config.inlay_hints)?
+        .into_iter()
+        .map(|hint| convert::to_inlay_hint(&line_map, hint))
+        .collect();
+    Ok(Some(hints))
+}
+
+pub(crate) fn document_symbol(
+    snap: StateSnapshot,
+    params: DocumentSymbolParams,
+) -> Result<Option<DocumentSymbolResponse>> {
+    let (file, line_map) = convert::from_file(&snap.vfs(), &params.text_document)?;
+    let symbols = snap
+        .analysis
+        .document_symbols(file)?
+        .into_iter()
+        .map(|symbol| convert::to_document_symbol(&line_map, symbol))
+        .collect();
+    Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+}
+
+pub(crate) fn workspace_symbol(
+    snap: StateSnapshot,
+    params: WorkspaceSymbolParams,
+) -> Result<Option<WorkspaceSymbolResponse>> {
+    let symbols = snap.analysis.workspace_symbols(
+        &params.query,
+        snap.config.workspace_symbols_include_dependencies,
+    )?;
+    let vfs = snap.vfs();
+    let symbols = symbols
+        .into_iter()
+        .map(|symbol| convert::to_workspace_symbol(&vfs, symbol))
+        .collect();
+    Ok(Some(WorkspaceSymbolResponse::Nested(symbols)))
+}
+
 pub(crate) fn syntax_tree(snap: StateSnapshot, params: SyntaxTreeParams) -> Result<String> {
     let (file, _) = convert::from_file(&snap.vfs(), &params.text_document)?;
     let syntax_tree = snap.analysis.syntax_tree(file)?;
     Ok(syntax_tree)
 }
 
+pub(crate) fn todos(snap: StateSnapshot, (): ()) -> Result<Vec<lsp_ext::Todo>> {
+    let todos = snap.analysis.todos()?;
+    let vfs = snap.vfs();
+    Ok(todos
+        .into_iter()
+        .map(|todo| lsp_ext::Todo {
+            location: convert::to_location(&vfs, todo.frange),
+            message: todo.message,
+        })
+        .collect())
+}
+
 pub(crate) fn semantic_token_full(
     snap: StateSnapshot,
     params: SemanticTokensParams,
@@ -1,5 +1,5 @@
 use lsp_types::request::Request;
-use lsp_types::TextDocumentIdentifier;
+use lsp_types::{Location, TextDocumentIdentifier};
 use serde::{Deserialize, Serialize};
 
 pub enum SyntaxTree {}
@@ -15,3 +15,18 @@ impl Request for SyntaxTree {
 pub struct SyntaxTreeParams {
     pub text_document: TextDocumentIdentifier,
 }
+
+pub enum Todos {}
+
+impl Request for Todos {
+    type Params = ();
+    type Result = Vec<Todo>;
+    const METHOD: &'static str = "glas/todos";
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Todo {
+    pub location: Location,
+    pub message: Option<String>,
+}
@@ -161,14 +161,25 @@ impl Server {
             //// Requests ////
             // .request_snap::<req::Formatting>(handler::formatting)
             .request_snap::<req::GotoDefinition>(handler::goto_definition)
+            .request_snap::<req::GotoTypeDefinition>(handler::goto_type_definition)
             .request_snap::<req::Completion>(handler::completion)
             .request_snap::<req::HoverRequest>(handler::hover)
             .request_snap::<req::DocumentHighlightRequest>(handler::document_highlight)
             .request_snap::<req::References>(handler::references)
             .request_snap::<req::PrepareRenameRequest>(handler::prepare_rename)
             .request_snap::<req::Rename>(handler::rename)
+            .request_snap::<req::CodeActionRequest>(handler::code_action)
+            .request_snap::<req::CallHierarchyPrepare>(handler::prepare_call_hierarchy)
+            .request_snap::<req::CallHierarchyIncomingCalls>(handler::incoming_calls)
+            .request_snap::<req::CallHierarchyOutgoingCalls>(handler::outgoing_calls)
+            .request_snap::<req::FoldingRangeRequest>(handler::folding_range)
+            .request_snap::<req::SelectionRangeRequest>(handler::selection_range)
             .request_snap::<req::SignatureHelpRequest>(handler::signature_help)
+            .request_snap::<req::InlayHintRequest>(handler::inlay_hint)
+            .request_snap::<req::DocumentSymbolRequest>(handler::document_symbol)
+            .request_snap::<req::WorkspaceSymbolRequest>(handler::workspace_symbol)
             .request_snap::<lsp_ext::SyntaxTree>(handler::syntax_tree)
+            .request_snap::<lsp_ext::Todos>(handler::todos)
             .request_snap::<req::SemanticTokensFullRequest>(handler::semantic_token_full)
             .request_snap::<req::SemanticTokensRangeRequest>(handler::semantic_token_range)
             //// Events ////
@@ -605,7 +616,7 @@ impl Server {
         ControlFlow::Continue(())
     }
 
-    /// Spawn a task to (re)load the gleam workspace via `gleam.toml`
+    // Spawn a task to (re)load the gleam workspace via `gleam.toml`
     // fn spawn_load_gleam_workspace(&mut self) {
     //     let fut = task::spawn(Self::load_gleam_workspace(
     //         self.vfs.clone(),
@@ -658,7 +669,7 @@ impl Server {
             .into();
 
         let package = match seen.get(name.as_str()) {
-            Some(idx) => idx.clone(),
+            Some(idx) => *idx,
             None => {
                 let parent = root_path.parent();
                 let is_parent_packages = parent.map(|p| p.ends_with("packages")).unwrap_or(false);
@@ -933,7 +944,7 @@ impl Server {
 
         let mut package_roots = self.source_roots.clone();
         // if file is not loaded yet, insert package source root
-        if let Err(_) = vfs.file_for_path(&vpath) {
+        if vfs.file_for_path(&vpath).is_err() {
             if let Some(path) = vpath.as_path().and_then(find_gleam_project_parent) {
                 let _ = load_package_info(&path);
                 tracing::info!("Setting new sourceroot {:?}", path);
@@ -1045,11 +1056,11 @@ impl Server {
                 }
             }
         }
-        let source_roots = prefix_to_paths
+
+        prefix_to_paths
             .into_iter()
             .map(|(path, set)| SourceRoot::new(set, path))
-            .collect();
-        source_roots
+            .collect()
     }
 }
 
@@ -1195,7 +1206,7 @@ impl Drop for Progress {
 fn with_catch_unwind<T>(ctx: &str, f: impl FnOnce() -> Result<T> + UnwindSafe) -> Result<T> {
     static INSTALL_PANIC_HOOK: Once = Once::new();
     thread_local! {
-        static PANIC_LOCATION: Cell<String> = Cell::new(String::new());
+        static PANIC_LOCATION: Cell<String> = const { Cell::new(String::new()) };
     }
 
     INSTALL_PANIC_HOOK.call_once(|| {
@@ -1245,7 +1256,6 @@ fn error_to_response(err: anyhow::Error) -> ResponseError {
 pub struct StateSnapshot {
     pub(crate) analysis: Analysis,
     vfs: Arc<RwLock<Vfs>>,
-    #[allow(unused)]
     pub(crate) config: Arc<Config>,
 }
 
@@ -190,11 +190,13 @@ impl LineMap {
             let mut diffs = Vec::new();
             for (&b, pos) in bytes[start as usize..end as usize].iter().zip(0u32..) {
                 let diff = match b {
-                    0b0000_0000..=0b0111_1111 |                      // utf8_len == 1, utf16_len == 1
-                    0b1000_0000..=0b1011_1111 => continue,           // Continuation bytes.
+                    // utf8_len == 1, utf16_len == 1
+                    0b0000_0000..=0b0111_1111 => continue,
+                    // Continuation bytes.
+                    0b1000_0000..=0b1011_1111 => continue,
                     0b1100_0000..=0b1101_1111 => CodeUnitsDiff::One, // utf8_len == 2, utf16_len == 1
                     0b1110_0000..=0b1110_1111 => CodeUnitsDiff::Two, // utf8_len == 3, utf16_len == 1
-                    0b1111_0000.. => CodeUnitsDiff::Two,             // utf8_len == 4, utf16_len == 2
+                    0b1111_0000.. => CodeUnitsDiff::Two, // utf8_len == 4, utf16_len == 2
                 };
                 diffs.push((pos, diff));
             }
@@ -1,11 +1,11 @@
 //! Wrapper for `gleam`.
 use anyhow::{anyhow, Context, Result};
 use std::{
-    path::PathBuf,
+    path::Path,
     process::{Command, Stdio},
 };
 
-pub fn load_package_info(p: &PathBuf) -> Result<()> {
+pub fn load_package_info(p: &Path) -> Result<()> {
     let output = Command::new("gleam")
         .current_dir(p.parent().context("No parent")?)
         .args(["deps", "download"])
@@ -123,7 +123,7 @@ impl ModuleMap {
         self.module_names.get(&file).cloned()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (FileId, &'_ SmolStr)> + ExactSizeIterator + '_ {
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (FileId, &'_ SmolStr)> + '_ {
         self.module_names.iter().map(|(&file, path)| (file, path))
     }
 }
@@ -155,7 +155,7 @@ impl FileSet {
         &self.paths[&file]
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = (FileId, &'_ VfsPath)> + ExactSizeIterator + '_ {
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (FileId, &'_ VfsPath)> + '_ {
         self.paths.iter().map(|(&file, path)| (file, path))
     }
 }
@@ -194,7 +194,11 @@ impl SourceRoot {
         self.file_set.path_for_file(file)
     }
 
-    pub fn files(&self) -> impl Iterator<Item = (FileId, &'_ VfsPath)> + ExactSizeIterator + '_ {
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
+    pub fn files(&self) -> impl ExactSizeIterator<Item = (FileId, &'_ VfsPath)> + '_ {
         self.file_set.iter()
     }
 
@@ -245,6 +249,11 @@ impl PackageGraph {
         self.target = target;
     }
 
+    /// The target the workspace is compiled for.
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
     pub fn add_package(
         &mut self,
         display_name: SmolStr,
@@ -294,6 +303,15 @@ pub enum Target {
     Javascript,
 }
 
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Erlang => "Erlang".fmt(f),
+            Self::Javascript => "JavaScript".fmt(f),
+        }
+    }
+}
+
 impl From<&str> for Target {
     fn from(value: &str) -> Self {
         match value {
@@ -341,7 +359,7 @@ impl<T: Clone> InFile<&T> {
     }
 }
 
-impl<'a> InFile<&'a SyntaxNode> {
+impl InFile<&SyntaxNode> {
     pub fn ancestors(self) -> impl Iterator<Item = InFile<SyntaxNode>> + Clone {
         iter::successors(Some(self.cloned()), move |node| {
             node.value.parent().map(|parent| node.with_value(parent))
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, ops};
 
-use la_arena::{Arena, ArenaMap, Idx, IdxRange, RawIdx};
+use la_arena::{Arena, ArenaMap, RawIdx};
 use smol_str::SmolStr;
 use syntax::{
     ast::{self, AstNode, Name, StatementExpr},
@@ -10,8 +10,10 @@ use syntax::{
 use crate::{DefDatabase, Diagnostic, FileId, InFile};
 
 use super::{
-    module::{self, Clause, Expr, ExprId, Pattern, PatternId, Statement},
-    FunctionId,
+    module::{
+        self, BitArrayOption, BitArraySegment, Clause, Expr, ExprId, Pattern, PatternId, Statement,
+    },
+    ConstId, FunctionId,
 };
 
 pub type ExprPtr = AstPtr<ast::Expr>;
@@ -55,13 +57,11 @@ impl Body {
         self.exprs.shrink_to_fit();
     }
 
-    pub fn exprs(&self) -> impl Iterator<Item = (ExprId, &'_ Expr)> + ExactSizeIterator + '_ {
+    pub fn exprs(&self) -> impl ExactSizeIterator<Item = (ExprId, &'_ Expr)> + '_ {
         self.exprs.iter()
     }
 
-    pub fn patterns(
-        &self,
-    ) -> impl Iterator<Item = (PatternId, &'_ Pattern)> + ExactSizeIterator + '_ {
+    pub fn patterns(&self) -> impl ExactSizeIterator<Item = (PatternId, &'_ Pattern)> + '_ {
         self.patterns.iter()
     }
 }
@@ -158,6 +158,25 @@ pub(super) fn lower(db: &dyn DefDatabase, function_id: FunctionId) -> (Body, Bod
     (ctx.body, ctx.source_map)
 }
 
+/// Constants are lowered like a function without parameters, the annotation
+/// takes the place of the return type.
+pub(super) fn lower_const(db: &dyn DefDatabase, const_id: ConstId) -> (Body, BodySourceMap) {
+    let loc = db.lookup_intern_const(const_id);
+    let module = db.module_items(loc.file_id);
+    let parse = db.parse(loc.file_id);
+    let ast = module[loc.value].ast_ptr.to_node(&parse.syntax_node());
+
+    let mut ctx = BodyLowerCtx {
+        file_id: loc.file_id,
+        source_map: BodySourceMap::default(),
+        body: Body::default(),
+    };
+    ctx.body.return_ = ast.annotation().map(module::typeref_from_ast);
+    ctx.body.body_expr = ctx.lower_expr_opt(ast.value());
+
+    (ctx.body, ctx.source_map)
+}
+
 struct BodyLowerCtx {
     body: Body,
     source_map: BodySourceMap,
@@ -217,24 +236,42 @@ impl BodyLowerCtx {
                 self.alloc_expr(Expr::Tuple { fields }, ptr)
             }
             ast::Expr::Hole(_) => self.alloc_expr(Expr::Hole, ptr),
+            ast::Expr::Todo(todo) => {
+                let message = todo.message().map(|e| self.lower_expr(e));
+                self.alloc_expr(Expr::Todo { message }, ptr)
+            }
+            ast::Expr::Panic(panic) => {
+                let message = panic.message().map(|e| self.lower_expr(e));
+                self.alloc_expr(Expr::Panic { message }, ptr)
+            }
             ast::Expr::ExprCall(call) => {
                 let func = self.lower_expr_opt(call.func());
+                let mut base = None;
                 let mut arg_ids: Vec<(Option<SmolStr>, ExprId)> = Vec::new();
                 if let Some(args) = call.arguments() {
                     for arg in args.args() {
+                        if let (None, Some(ast::Expr::ExprSpread(spread))) = (&base, arg.value()) {
+                            base = Some(self.lower_expr_opt(spread.expr()));
+                            continue;
+                        }
                         arg_ids.push((
                             arg.label().and_then(|t| t.text()),
                             self.lower_expr_opt(arg.value()),
                         ));
                     }
                 }
-                self.alloc_expr(
-                    Expr::Call {
+                let expr = match base {
+                    Some(base) => Expr::RecordUpdate {
+                        constructor: func,
+                        base,
+                        fields: arg_ids,
+                    },
+                    None => Expr::Call {
                         func,
                         args: arg_ids,
                     },
-                    ptr,
-                )
+                };
+                self.alloc_expr(expr, ptr)
             }
             ast::Expr::VariantConstructor(constr) => {
                 let name = constr
@@ -250,6 +287,11 @@ impl BodyLowerCtx {
                 let right = self.lower_expr_opt(e.rhs());
                 self.alloc_expr(Expr::Binary { op, left, right }, ptr)
             }
+            ast::Expr::UnaryOp(e) => {
+                let op = e.op_kind();
+                let expr = self.lower_expr_opt(e.arg());
+                self.alloc_expr(Expr::Unary { expr, op }, ptr)
+            }
             ast::Expr::Pipe(e) => {
                 let left = self.lower_expr_opt(e.lhs());
                 let right = self.lower_expr_opt(e.rhs());
@@ -261,21 +303,43 @@ impl BodyLowerCtx {
             ast::Expr::Literal(lit) => {
                 self.alloc_expr(lit.kind().map_or(Expr::Missing, Expr::Literal), ptr)
             }
-            ast::Expr::BitArray(_) => self.alloc_expr(Expr::BitArray, ptr),
+            ast::Expr::BitArray(bits) => {
+                let segments = bits
+                    .segments()
+                    .map(|segment| BitArraySegment {
+                        value: self.lower_expr_opt(segment.value()),
+                        options: self.lower_bit_array_options(&segment),
+                    })
+                    .collect();
+                self.alloc_expr(Expr::BitArray { segments }, ptr)
+            }
             ast::Expr::Case(case) => {
                 let subjects = case.subjects().map(|s| self.lower_expr(s)).collect();
 
                 let clauses = case
                     .clauses()
                     .map(|clause| {
-                        let mut patterns = Vec::new();
-                        for pat in clause.patterns() {
-                            patterns.push(self.lower_pattern_opt(pat.patterns().next()));
+                        // `1, 2 | 3, 4` parses as `1`, `2 | 3` and `4`, so every `|` starts a
+                        // new row of patterns.
+                        let mut rows = vec![Vec::new()];
+                        for alternative in clause.patterns() {
+                            let mut pats = alternative.patterns();
+                            let first = self.lower_pattern_opt(pats.next());
+                            rows.last_mut().unwrap().push(first);
+                            for pat in pats {
+                                rows.push(vec![self.lower_pattern(pat)]);
+                            }
                         }
+                        let mut rows = rows.into_iter();
+                        let patterns = rows.next().unwrap_or_default();
+
+                        let guard = clause.guard().map(|g| self.lower_expr_opt(g.expr()));
 
                         Clause {
-                            expr: self.lower_expr_opt(clause.body()),
                             patterns,
+                            alternative_patterns: rows.collect(),
+                            guard,
+                            expr: self.lower_expr_opt(clause.body()),
                         }
                     })
                     .collect();
@@ -322,18 +386,20 @@ impl BodyLowerCtx {
             }
             ast::Expr::Lambda(lambda) => {
                 if let Some(param_list) = lambda.param_list() {
-                    let start = self.next_pattern_idx();
+                    let mut params = Vec::new();
                     for param in param_list.params() {
                         if let Some(pattern) = param.pattern() {
-                            let _pat_id = self.lower_pattern(pattern);
+                            let pat_id = self.lower_pattern(pattern);
+                            params.push((pat_id, module::typeref_from_ast_opt(param.ty())));
                         }
                     }
-                    let end = self.next_pattern_idx();
+                    let return_ = lambda.return_type().map(module::typeref_from_ast);
                     let body = self.lower_expr_opt(lambda.body().map(ast::Expr::Block));
                     return self.alloc_expr(
                         Expr::Lambda {
                             body,
-                            params: IdxRange::new(start..end),
+                            params,
+                            return_,
                         },
                         ptr,
                     );
@@ -344,7 +410,6 @@ impl BodyLowerCtx {
                 let elements = l.elements().map(|e| self.lower_expr(e)).collect();
                 self.alloc_expr(Expr::List { elements }, ptr)
             }
-            _ => self.alloc_expr(Expr::Missing, ptr),
         }
     }
 
@@ -364,7 +429,9 @@ impl BodyLowerCtx {
                     let pattern = self.lower_pattern(pattern);
                     statements.push(Statement::Let {
                         pattern,
+                        annotation: module::typeref_from_ast_opt(stmt.annotation()),
                         body: expr_id,
+                        assert: stmt.is_assert(),
                     });
                 }
             }
@@ -379,7 +446,8 @@ impl BodyLowerCtx {
                     let mut patterns = Vec::new();
                     for assignment in use_.assignments() {
                         if let Some(pattern) = assignment.pattern() {
-                            patterns.push(self.lower_pattern(pattern));
+                            let annotation = module::typeref_from_ast_opt(assignment.annotation());
+                            patterns.push((self.lower_pattern(pattern), annotation));
                         }
                     }
                     let expr_id = self.lower_expr(expr);
@@ -451,18 +519,63 @@ impl BodyLowerCtx {
                 let pattern = self.lower_pattern_opt(it.name());
                 Pattern::Concat { pattern }
             }
+            ast::Pattern::PatternBitArray(bits) => {
+                let segments = bits
+                    .segments()
+                    .map(|segment| BitArraySegment {
+                        value: self.lower_pattern_opt(segment.pattern()),
+                        options: self.lower_bit_array_options(&segment),
+                    })
+                    .collect();
+                Pattern::BitArray { segments }
+            }
+            ast::Pattern::AlternativePattern(it) => return self.lower_alternative_pattern(it),
         };
         self.alloc_pattern(pat, ptr)
     }
 
+    fn lower_bit_array_options(&mut self, segment: &ast::BitArraySegment) -> Vec<BitArrayOption> {
+        let mut options = Vec::new();
+        for option in segment.options() {
+            let name = option.name();
+            let option = match name.as_deref() {
+                // `value:8` is shorthand for `value:size(8)`
+                None | Some("size") => BitArrayOption::Size(self.lower_expr_opt(option.arg())),
+                Some("unit") => {
+                    let Some(unit) = option
+                        .arg()
+                        .and_then(|arg| arg.syntax().text().to_string().parse().ok())
+                    else {
+                        continue;
+                    };
+                    BitArrayOption::Unit(unit)
+                }
+                Some(name) => match BitArrayOption::from_name(name) {
+                    Some(opt) => opt,
+                    None => continue,
+                },
+            };
+            options.push(option);
+        }
+        options
+    }
+
+    fn lower_alternative_pattern(&mut self, alternative: ast::AlternativePattern) -> PatternId {
+        let mut alternatives = alternative.patterns();
+        let first = self.lower_pattern_opt(alternatives.next());
+        let mut patterns = vec![first];
+        patterns.extend(alternatives.map(|pat| self.lower_pattern(pat)));
+        if patterns.len() == 1 {
+            return first;
+        }
+        let ptr = AstPtr::new(&ast::Pattern::AlternativePattern(alternative));
+        self.alloc_pattern(Pattern::AlternativePattern { patterns }, ptr)
+    }
+
     fn lower_pattern_opt(&mut self, pat: Option<ast::Pattern>) -> PatternId {
         match pat {
             Some(pat) => self.lower_pattern(pat),
             None => self.missing_pat(),
         }
     }
-
-    fn next_pattern_idx(&self) -> Idx<Pattern> {
-        Idx::from_raw(RawIdx::from(self.body.patterns.len() as u32))
-    }
 }
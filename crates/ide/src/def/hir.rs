@@ -6,14 +6,16 @@ use syntax::ast::{self, AstNode, HasDocParts};
 use crate::{
     impl_from,
     ty::{self, TyDatabase},
-    DefDatabase, FileId, InFile, ModuleMap, SourceRootId,
+    DefDatabase, FileId, InFile, ModuleMap, SourceRootId, Target,
 };
 
 use super::{
-    hir_def::{AdtId, ConstId, ImportId, LocalFieldId, LocalVariantId, TypeAliasId, VariantId},
+    hir_def::{
+        AdtId, ConstId, ImportId, LocalFieldId, LocalVariantId, ModuleDefId, TypeAliasId, VariantId,
+    },
     module::{
-        AdtData, ConstData, FieldData, FunctionData, ImportData, Param, PatternId, TypeAliasData,
-        TypeRef, VariantData,
+        AdtData, Attrs, ConstData, External, FieldData, FunctionData, ImportData, Param, PatternId,
+        TypeAliasData, TypeRef, VariantData,
     },
     resolver::resolver_for_toplevel,
     semantics::Definition,
@@ -71,10 +73,9 @@ impl Package {
         Arc::new(module_map)
     }
 
-    // pub fn target(self, db: &dyn DefDatabase) -> crate::base::Target {
-    //     db.source_root_package_info(SourceRootId(0))
-    //         .map_or(crate::base::Target::default(), |s| s.target.clone())
-    // }
+    pub fn target(self, db: &dyn DefDatabase) -> Target {
+        db.package_graph().target().clone()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -123,6 +124,59 @@ pub enum ModuleDef {
     ModuleConstant(ModuleConstant),
 }
 
+impl From<ModuleDefId> for ModuleDef {
+    fn from(id: ModuleDefId) -> Self {
+        match id {
+            ModuleDefId::FunctionId(id) => ModuleDef::Function(Function { id }),
+            ModuleDefId::AdtId(id) => ModuleDef::Adt(Adt { id }),
+            ModuleDefId::VariantId(id) => ModuleDef::Variant(Variant {
+                parent: id.parent,
+                id: id.local_id,
+            }),
+            ModuleDefId::TypeAliasId(id) => ModuleDef::TypeAlias(TypeAlias { id }),
+            ModuleDefId::ModuleConstant(id) => ModuleDef::ModuleConstant(ModuleConstant { id }),
+        }
+    }
+}
+
+impl ModuleDef {
+    pub fn name(self, db: &dyn DefDatabase) -> SmolStr {
+        match self {
+            ModuleDef::Function(it) => it.name(db),
+            ModuleDef::Variant(it) => it.name(db),
+            ModuleDef::Adt(it) => it.name(db),
+            ModuleDef::TypeAlias(it) => it.name(db),
+            ModuleDef::ModuleConstant(it) => it.name(db),
+        }
+    }
+
+    pub fn module(self, db: &dyn DefDatabase) -> Module {
+        match self {
+            ModuleDef::Function(it) => it.module(db),
+            ModuleDef::Variant(it) => it.module(db),
+            ModuleDef::Adt(it) => it.module(db),
+            ModuleDef::TypeAlias(it) => it.module(db),
+            ModuleDef::ModuleConstant(it) => it.module(db),
+        }
+    }
+
+    /// Constructors share the attributes of their type.
+    pub fn attrs(self, db: &dyn DefDatabase) -> Attrs {
+        match self {
+            ModuleDef::Function(it) => it.attrs(db),
+            ModuleDef::Variant(it) => it.parent().attrs(db),
+            ModuleDef::Adt(it) => it.attrs(db),
+            ModuleDef::TypeAlias(it) => it.attrs(db),
+            ModuleDef::ModuleConstant(it) => it.attrs(db),
+        }
+    }
+
+    /// `@internal` items are hidden from every package but their own.
+    pub fn is_internal_to(self, db: &dyn DefDatabase, package: Package) -> bool {
+        self.attrs(db).is_internal && self.module(db).package(db) != package
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TypeAlias {
     pub(crate) id: TypeAliasId,
@@ -144,6 +198,10 @@ impl TypeAlias {
     pub fn module(&self, db: &dyn DefDatabase) -> Module {
         db.lookup_intern_type_alias(self.id).file_id.into()
     }
+
+    pub fn attrs(self, db: &dyn DefDatabase) -> Attrs {
+        self.data(db).attrs
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -192,6 +250,19 @@ impl Adt {
         fields
     }
 
+    pub fn is_opaque(self, db: &dyn DefDatabase) -> bool {
+        self.data(db).is_opaque
+    }
+
+    /// Whether the constructors and fields are hidden from `module`.
+    pub fn is_opaque_to(self, db: &dyn DefDatabase, module: Module) -> bool {
+        self.is_opaque(db) && self.module(db) != module
+    }
+
+    pub fn attrs(self, db: &dyn DefDatabase) -> Attrs {
+        self.data(db).attrs
+    }
+
     pub fn data(&self, db: &dyn DefDatabase) -> AdtData {
         let adt = db.lookup_intern_adt(self.id);
         let module_items = db.module_items(adt.file_id);
@@ -298,11 +369,29 @@ impl Function {
         self.data(db).params.clone()
     }
 
+    /// The declared signature if it is complete, so users don't wait on the body's inference.
     pub fn ty(&self, db: &dyn TyDatabase) -> ty::Ty {
+        if let Some(ty) = db.function_signature(self.id) {
+            return ty;
+        }
         let infer = db.infer_function(self.id);
         infer.fn_ty.clone()
     }
 
+    pub fn attrs(self, db: &dyn DefDatabase) -> Attrs {
+        self.data(db).attrs
+    }
+
+    pub fn externals(self, db: &dyn DefDatabase) -> Vec<External> {
+        self.data(db).externals
+    }
+
+    /// Whether the function can run on `target`, through its body or an external.
+    pub fn supports_target(self, db: &dyn DefDatabase, target: &Target) -> bool {
+        let data = self.data(db);
+        data.has_body || data.externals.iter().any(|it| it.target == *target)
+    }
+
     fn data(self, db: &dyn DefDatabase) -> FunctionData {
         let func = db.lookup_intern_function(self.id);
         db.module_items(func.file_id)[func.value].clone()
@@ -335,6 +424,14 @@ impl ModuleConstant {
         self.data(db).name.clone()
     }
 
+    pub fn ty(self, db: &dyn TyDatabase) -> ty::Ty {
+        db.infer_const(self.id).fn_ty.clone()
+    }
+
+    pub fn attrs(self, db: &dyn DefDatabase) -> Attrs {
+        self.data(db).attrs
+    }
+
     fn data(self, db: &dyn DefDatabase) -> ConstData {
         let func = db.lookup_intern_const(self.id);
         db.module_items(func.file_id)[func.value].clone()
@@ -454,6 +551,19 @@ pub enum BuiltIn {
 }
 
 impl BuiltIn {
+    /// The prelude types, which are in scope without an import.
+    pub const TYPES: &'static [&'static str] = &[
+        "Int",
+        "Float",
+        "String",
+        "BitArray",
+        "UtfCodepoint",
+        "Bool",
+        "Nil",
+        "List",
+        "Result",
+    ];
+
     pub fn values() -> HashMap<SmolStr, BuiltIn> {
         [
             ("Nil".into(), BuiltIn::Nil),
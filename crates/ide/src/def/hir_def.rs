@@ -143,11 +143,13 @@ macro_rules! impl_intern_key {
     };
 }
 
+#[allow(dead_code)]
 trait Intern {
     type ID;
     fn intern(self, db: &dyn DefDatabase) -> Self::ID;
 }
 
+#[allow(dead_code)]
 pub trait Lookup {
     type Data;
     fn lookup(&self, db: &dyn DefDatabase) -> Self::Data;
@@ -1,11 +1,11 @@
 use std::{collections::HashMap, ops::Index};
 
-use crate::Diagnostic;
+use crate::{Diagnostic, Target};
 
 use super::{
     module::{
-        self, AdtData, ConstData, FieldData, FunctionData, ImportData, ModuleImport, Param,
-        TypeAliasData, VariantData, Visibility,
+        self, AdtData, Attrs, ConstData, External, FieldData, FunctionData, ImportData,
+        ModuleImport, Param, TypeAliasData, VariantData, Visibility,
     },
     AstPtr,
 };
@@ -34,41 +34,37 @@ pub struct ModuleItemData {
 impl ModuleItemData {
     pub fn unqualified_imports(
         &self,
-    ) -> impl Iterator<Item = (Idx<ImportData>, &ImportData)> + ExactSizeIterator + '_ {
+    ) -> impl ExactSizeIterator<Item = (Idx<ImportData>, &ImportData)> + '_ {
         self.unqualified_imports.iter()
     }
 
-    pub fn adts(&self) -> impl Iterator<Item = (Idx<AdtData>, &AdtData)> + ExactSizeIterator + '_ {
+    pub fn adts(&self) -> impl ExactSizeIterator<Item = (Idx<AdtData>, &AdtData)> + '_ {
         self.adts.iter()
     }
 
     pub fn type_alias(
         &self,
-    ) -> impl Iterator<Item = (Idx<TypeAliasData>, &TypeAliasData)> + ExactSizeIterator + '_ {
+    ) -> impl ExactSizeIterator<Item = (Idx<TypeAliasData>, &TypeAliasData)> + '_ {
         self.type_alias.iter()
     }
 
-    pub fn variants(
-        &self,
-    ) -> impl Iterator<Item = (Idx<VariantData>, &VariantData)> + ExactSizeIterator + '_ {
+    pub fn variants(&self) -> impl ExactSizeIterator<Item = (Idx<VariantData>, &VariantData)> + '_ {
         self.variants.iter()
     }
 
-    pub fn constants(
-        &self,
-    ) -> impl Iterator<Item = (Idx<ConstData>, &ConstData)> + ExactSizeIterator + '_ {
+    pub fn constants(&self) -> impl ExactSizeIterator<Item = (Idx<ConstData>, &ConstData)> + '_ {
         self.constants.iter()
     }
 
     pub fn functions(
         &self,
-    ) -> impl Iterator<Item = (Idx<FunctionData>, &FunctionData)> + ExactSizeIterator + '_ {
+    ) -> impl ExactSizeIterator<Item = (Idx<FunctionData>, &FunctionData)> + '_ {
         self.functions.iter()
     }
 
     pub fn module_imports(
         &self,
-    ) -> impl Iterator<Item = (Idx<ModuleImport>, &ModuleImport)> + ExactSizeIterator + '_ {
+    ) -> impl ExactSizeIterator<Item = (Idx<ModuleImport>, &ModuleImport)> + '_ {
         self.module_imports.iter()
     }
 }
@@ -278,10 +274,31 @@ impl LowerCtx {
             Visibility::Private
         };
 
+        let externals = fun
+            .externals()
+            .filter_map(|attr| {
+                let target = match attr.target()?.text() {
+                    "erlang" => Target::Erlang,
+                    "javascript" => Target::Javascript,
+                    _ => return None,
+                };
+                let unquote =
+                    |tok: syntax::SyntaxToken| SmolStr::from(tok.text().trim_matches('"'));
+                Some(External {
+                    target,
+                    module: unquote(attr.module()?),
+                    function: unquote(attr.function()?),
+                })
+            })
+            .collect();
+
         Some(self.alloc_function(FunctionData {
             name: fun.name()?.text()?,
             params,
             visibility,
+            externals,
+            has_body: fun.body().is_some(),
+            attrs: Attrs::from_ast(fun.attributes()),
             ast_ptr,
         }))
     }
@@ -321,6 +338,8 @@ impl LowerCtx {
             variants: constructors,
             params: generic_params,
             visibility,
+            is_opaque: ct.is_opaque(),
+            attrs: Attrs::from_ast(ct.attributes()),
             ast_ptr,
         }))
     }
@@ -338,6 +357,7 @@ impl LowerCtx {
         Some(self.alloc_const(ConstData {
             name,
             visibility,
+            attrs: Attrs::from_ast(it.attributes()),
             ast_ptr,
         }))
     }
@@ -364,6 +384,7 @@ impl LowerCtx {
             body,
             params: generic_params,
             visibility,
+            attrs: Attrs::from_ast(alias.attributes()),
             ast_ptr,
         }))
     }
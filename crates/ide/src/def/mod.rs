@@ -9,6 +9,7 @@ mod search;
 pub mod semantics;
 pub mod source;
 pub mod source_analyzer;
+mod symbol_index;
 
 use std::sync::Arc;
 
@@ -31,6 +32,7 @@ use self::scope::{
 };
 pub use resolver::resolver_for_expr;
 pub use search::SearchScope;
+pub use symbol_index::{FileSymbol, SymbolKind};
 
 #[salsa::query_group(InternDatabaseStorage)]
 pub trait InternDatabase: SourceDatabase {
@@ -60,6 +62,10 @@ pub trait DefDatabase: SourceDatabase + InternDatabase {
 
     fn body_source_map(&self, funcion_id: FunctionId) -> Arc<BodySourceMap>;
 
+    fn const_body_with_source_map(&self, const_id: ConstId) -> (Arc<Body>, Arc<BodySourceMap>);
+
+    fn const_body(&self, const_id: ConstId) -> Arc<Body>;
+
     fn module_items(&self, file_id: FileId) -> Arc<ModuleItemData>;
 
     #[salsa::invoke(ExprScopes::expr_scopes_query)]
@@ -74,6 +80,9 @@ pub trait DefDatabase: SourceDatabase + InternDatabase {
 
     #[salsa::invoke(dependency_order_query)]
     fn dependency_order(&self, file_id: FileId) -> Vec<Vec<FunctionId>>;
+
+    #[salsa::invoke(symbol_index::module_symbols_query)]
+    fn module_symbols(&self, file_id: FileId) -> Arc<Vec<FileSymbol>>;
 }
 
 fn parse(db: &dyn DefDatabase, file_id: FileId) -> Parse {
@@ -104,6 +113,20 @@ fn body(db: &dyn DefDatabase, function_id: FunctionId) -> Arc<Body> {
     db.body_with_source_map(function_id).0
 }
 
+fn const_body_with_source_map(
+    db: &dyn DefDatabase,
+    const_id: ConstId,
+) -> (Arc<Body>, Arc<BodySourceMap>) {
+    let (mut body, mut body_source_map) = body::lower_const(db, const_id);
+    body.shink_to_fit();
+    body_source_map.shrink_to_fit();
+    (Arc::new(body), Arc::new(body_source_map))
+}
+
+fn const_body(db: &dyn DefDatabase, const_id: ConstId) -> Arc<Body> {
+    db.const_body_with_source_map(const_id).0
+}
+
 fn module_scope(db: &dyn DefDatabase, file_id: FileId) -> Arc<ModuleScope> {
     db.module_scope_with_map(file_id).0
 }
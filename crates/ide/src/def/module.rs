@@ -3,10 +3,12 @@ use std::{collections::HashMap, fmt::Display, sync::Arc};
 use la_arena::{Idx, IdxRange};
 use smol_str::SmolStr;
 use syntax::{
-    ast::{self, BinaryOpKind, LiteralKind},
+    ast::{self, BinaryOpKind, LiteralKind, UnaryOpKind},
     AstPtr,
 };
 
+use crate::{impl_from, Target};
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct AdtData {
     pub name: SmolStr,
@@ -16,6 +18,8 @@ pub struct AdtData {
 
     pub params: Vec<TypeRef>,
     pub visibility: Visibility,
+    pub is_opaque: bool,
+    pub attrs: Attrs,
 
     pub ast_ptr: AstPtr<ast::Adt>,
 }
@@ -28,6 +32,7 @@ pub struct TypeAliasData {
 
     pub params: Vec<TypeRef>,
     pub visibility: Visibility,
+    pub attrs: Attrs,
 
     pub ast_ptr: AstPtr<ast::TypeAlias>,
 }
@@ -48,21 +53,62 @@ pub struct FieldData {
     pub ast_ptr: AstPtr<ast::VariantField>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub struct TypeParam(SmolStr);
-
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FunctionData {
     pub name: SmolStr,
     pub params: Vec<Param>,
     pub visibility: Visibility,
+    pub externals: Vec<External>,
+    pub has_body: bool,
+    pub attrs: Attrs,
     pub ast_ptr: AstPtr<ast::Function>,
 }
 
+/// The attributes which change how an item is presented to its users.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Attrs {
+    /// The message of `@deprecated("message")`.
+    pub deprecated: Option<SmolStr>,
+    /// `@internal` items are public, but not meant for other packages.
+    pub is_internal: bool,
+}
+
+impl Attrs {
+    pub fn from_ast(attrs: impl Iterator<Item = ast::Attribute>) -> Self {
+        let mut this = Self::default();
+        for attr in attrs {
+            let Some(name) = attr.name() else {
+                continue;
+            };
+            match name.text() {
+                "deprecated" => {
+                    let message = attr.args().next();
+                    this.deprecated =
+                        Some(message.map_or_else(SmolStr::default, |it| {
+                            it.text().trim_matches('"').into()
+                        }));
+                }
+                "internal" => this.is_internal = true,
+                _ => {}
+            }
+        }
+        this
+    }
+}
+
+/// An `@external(target, "module", "function")` implementation of a function.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct External {
+    pub target: Target,
+    pub module: SmolStr,
+    pub function: SmolStr,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ConstData {
     pub name: SmolStr,
     pub visibility: Visibility,
+    pub attrs: Attrs,
     pub ast_ptr: AstPtr<ast::ModuleConstant>,
 }
 
@@ -110,21 +156,35 @@ pub enum Visibility {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Clause {
+    /// One pattern per subject.
     pub patterns: Vec<PatternId>,
-    // pub guard: Option<ExprId>,
+    /// The rows after a `|`, like `3, 4` in `1, 2 | 3, 4 ->`.
+    pub alternative_patterns: Vec<Vec<PatternId>>,
+    pub guard: Option<ExprId>,
     pub expr: ExprId,
 }
 
+impl Clause {
+    /// The first row of patterns followed by its alternatives.
+    pub fn rows(&self) -> impl Iterator<Item = &[PatternId]> {
+        std::iter::once(&self.patterns[..])
+            .chain(self.alternative_patterns.iter().map(Vec::as_slice))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Statement {
     Let {
-        pattern: PatternId, // Should be (PatternId, Option<TypeRef>)
-        // type_ann: Type
+        pattern: PatternId,
+        // `let a: Int = ...` pins down the type of the value
+        annotation: Option<TypeRef>,
         body: ExprId,
+        // `let assert` allows refutable patterns
+        assert: bool,
     },
     Use {
-        // Should be (PatternId, Option<TypeRef>)
-        patterns: Vec<PatternId>,
+        // `use a: Int, b <- f()` binds the callback parameters, with optional annotations
+        patterns: Vec<(PatternId, Option<TypeRef>)>,
         expr: ExprId,
     },
     Expr {
@@ -137,8 +197,17 @@ pub type ExprId = Idx<Expr>;
 pub enum Expr {
     Missing,
     Hole,
+    // `todo as "message"` and `panic as "message"`
+    Todo {
+        message: Option<ExprId>,
+    },
+    Panic {
+        message: Option<ExprId>,
+    },
     Literal(LiteralKind),
-    BitArray,
+    BitArray {
+        segments: Vec<BitArraySegment<ExprId>>,
+    },
     Block {
         stmts: Vec<Statement>,
     },
@@ -147,6 +216,10 @@ pub enum Expr {
         right: ExprId,
         op: Option<BinaryOpKind>,
     },
+    Unary {
+        expr: ExprId,
+        op: Option<UnaryOpKind>,
+    },
     Tuple {
         fields: Vec<ExprId>,
     },
@@ -168,6 +241,12 @@ pub enum Expr {
         func: ExprId,
         args: Vec<(Option<SmolStr>, ExprId)>,
     },
+    // `Record(..base, label: value)`
+    RecordUpdate {
+        constructor: ExprId,
+        base: ExprId,
+        fields: Vec<(Option<SmolStr>, ExprId)>,
+    },
     List {
         elements: Vec<ExprId>,
     },
@@ -177,8 +256,8 @@ pub enum Expr {
     },
     Lambda {
         body: ExprId,
-        // ToDo: this should be (PatternId, Option<TypeRef>)
-        params: IdxRange<Pattern>,
+        params: Vec<(PatternId, Option<TypeRef>)>,
+        return_: Option<TypeRef>,
     },
     Spread {
         expr: ExprId,
@@ -193,6 +272,15 @@ pub enum Expr {
 
 pub type PatternId = Idx<Pattern>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExprOrPatternId {
+    ExprId(ExprId),
+    PatternId(PatternId),
+}
+
+impl_from!(ExprId, PatternId for ExprOrPatternId);
+
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Pattern {
     Missing,
@@ -227,10 +315,62 @@ pub enum Pattern {
     Concat {
         pattern: PatternId,
     },
+    BitArray {
+        segments: Vec<BitArraySegment<PatternId>>,
+    },
+}
+
+/// A single `value:option-option` segment of a bit array expression or pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitArraySegment<T> {
+    pub value: T,
+    pub options: Vec<BitArrayOption>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Label(pub SmolStr);
+pub enum BitArrayOption {
+    Bytes,
+    Int,
+    Float,
+    Bits,
+    Utf8,
+    Utf16,
+    Utf32,
+    Utf8Codepoint,
+    Utf16Codepoint,
+    Utf32Codepoint,
+    Signed,
+    Unsigned,
+    Big,
+    Little,
+    Native,
+    Size(ExprId),
+    Unit(u32),
+}
+
+impl BitArrayOption {
+    pub fn from_name(name: &str) -> Option<Self> {
+        let opt = match name {
+            "bytes" | "binary" => Self::Bytes,
+            "int" => Self::Int,
+            "float" => Self::Float,
+            "bits" | "bit_array" | "bit_string" => Self::Bits,
+            "utf8" => Self::Utf8,
+            "utf16" => Self::Utf16,
+            "utf32" => Self::Utf32,
+            "utf8_codepoint" => Self::Utf8Codepoint,
+            "utf16_codepoint" => Self::Utf16Codepoint,
+            "utf32_codepoint" => Self::Utf32Codepoint,
+            "signed" => Self::Signed,
+            "unsigned" => Self::Unsigned,
+            "big" => Self::Big,
+            "little" => Self::Little,
+            "native" => Self::Native,
+            _ => return None,
+        };
+        Some(opt)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TypeRef {
@@ -271,6 +411,22 @@ pub fn write_joined<T: Display>(
     Ok(())
 }
 
+impl TypeRef {
+    /// Whether the type is written out in full, without any `_` left to infer or parts that
+    /// failed to parse.
+    pub fn is_complete(&self) -> bool {
+        match self {
+            TypeRef::Hole | TypeRef::Unknown => false,
+            TypeRef::Generic { .. } => true,
+            TypeRef::Function { params, return_ } => {
+                params.iter().all(TypeRef::is_complete) && return_.is_complete()
+            }
+            TypeRef::Adt { params, .. } => params.iter().all(TypeRef::is_complete),
+            TypeRef::Tuple { fields } => fields.iter().all(TypeRef::is_complete),
+        }
+    }
+}
+
 impl Display for TypeRef {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -284,7 +440,7 @@ impl Display for TypeRef {
             }
             TypeRef::Adt { name, params, .. } => {
                 write!(f, "{}", name)?;
-                if params.len() > 0 {
+                if !params.is_empty() {
                     write!(f, "(")?;
                     write_joined(f, params.iter(), ", ")?;
                     write!(f, ")")?;
@@ -6,7 +6,7 @@ use smol_str::SmolStr;
 use crate::{DefDatabase, FileId};
 
 use super::{
-    hir::{Adt, BuiltIn, Function, Local, Module, ModuleConstant, TypeAlias, Variant},
+    hir::{Adt, BuiltIn, Function, Local, Module, ModuleConstant, ModuleDef, TypeAlias, Variant},
     hir_def::ModuleDefId,
     module::ExprId,
     scope::{ModuleScope, ScopeId},
@@ -65,6 +65,20 @@ pub enum ResolveResult {
     BuiltIn(BuiltIn), // BuiltIn()
 }
 
+impl ResolveResult {
+    /// The module level definition this resolved to, if any.
+    pub fn module_def(&self) -> Option<ModuleDef> {
+        match *self {
+            ResolveResult::Function(it) => Some(ModuleDef::Function(it)),
+            ResolveResult::ModuleConstant(it) => Some(ModuleDef::ModuleConstant(it)),
+            ResolveResult::Variant(it) => Some(ModuleDef::Variant(it)),
+            ResolveResult::Adt(it) => Some(ModuleDef::Adt(it)),
+            ResolveResult::TypeAlias(it) => Some(ModuleDef::TypeAlias(it)),
+            ResolveResult::Local(_) | ResolveResult::Module(_) | ResolveResult::BuiltIn(_) => None,
+        }
+    }
+}
+
 impl Resolver {
     pub fn values_names_in_scope(&self) -> IndexMap<SmolStr, ResolveResult> {
         let mut map = ScopeNames::default();
@@ -16,7 +16,7 @@ use super::{
         AdtId, AdtLoc, ConstId, ConstLoc, FunctionLoc, ImportId, ImportLoc, ModuleDefId,
         TypeAliasId, TypeAliasLoc, VariantId,
     },
-    module::{Clause, Expr, ExprId, ImportData, Pattern, PatternId, Statement, Visibility},
+    module::{BitArrayOption, Expr, ExprId, ImportData, Pattern, PatternId, Statement, Visibility},
     resolver::ResolveResult,
     resolver_for_expr, FunctionId, ModuleItemData,
 };
@@ -159,12 +159,17 @@ pub fn module_scope_with_map_query(
                 .insert(variant.ast_ptr.clone(), variant_id);
             scope.values.insert(name.clone(), def.clone());
 
-            //use visibility of adt
+            // Constructors of opaque types never leave their module.
+            let visibility = if adt.is_opaque {
+                Visibility::Private
+            } else {
+                adt.visibility.clone()
+            };
             scope
                 .declarations
                 .entry(name.clone())
                 .or_default()
-                .push((def, adt.visibility.clone()));
+                .push((def, visibility));
         }
     }
 
@@ -239,19 +244,17 @@ impl ModuleScope {
         self.modules.get(name)
     }
 
-    pub fn values(
-        &self,
-    ) -> impl Iterator<Item = (&SmolStr, &ModuleDefId)> + ExactSizeIterator + '_ {
+    pub fn values(&self) -> impl ExactSizeIterator<Item = (&SmolStr, &ModuleDefId)> + '_ {
         self.values.iter()
     }
 
-    pub fn types(&self) -> impl Iterator<Item = (&SmolStr, &ModuleDefId)> + ExactSizeIterator + '_ {
+    pub fn types(&self) -> impl ExactSizeIterator<Item = (&SmolStr, &ModuleDefId)> + '_ {
         self.types.iter()
     }
 
     pub fn declarations(
         &self,
-    ) -> impl Iterator<Item = &Vec<(ModuleDefId, Visibility)>> + ExactSizeIterator + '_ {
+    ) -> impl ExactSizeIterator<Item = &Vec<(ModuleDefId, Visibility)>> + '_ {
         self.declarations.iter().map(|v| v.1)
     }
 
@@ -280,7 +283,7 @@ impl ModuleScope {
         items
             .iter()
             .filter(|i| i.1 == Visibility::Public)
-            .map(|i| (is_type.clone(), i.0.clone()))
+            .map(|i| (*is_type, i.0.clone()))
             .collect()
     }
 }
@@ -358,6 +361,17 @@ impl ExprScopes {
                 }
                 self.traverse_expr(body, *func, scope);
             }
+            Expr::RecordUpdate {
+                constructor,
+                base,
+                fields,
+            } => {
+                self.traverse_expr(body, *constructor, scope);
+                self.traverse_expr(body, *base, scope);
+                for field in fields {
+                    self.traverse_expr(body, field.1, scope);
+                }
+            }
             Expr::Spread { expr } => {
                 self.traverse_expr(body, *expr, scope);
             }
@@ -365,6 +379,14 @@ impl ExprScopes {
                 self.traverse_expr(body, *left, scope);
                 self.traverse_expr(body, *right, scope);
             }
+            Expr::Unary { expr, op: _ } => {
+                self.traverse_expr(body, *expr, scope);
+            }
+            Expr::Todo { message } | Expr::Panic { message } => {
+                if let Some(message) = message {
+                    self.traverse_expr(body, *message, scope);
+                }
+            }
             Expr::Pipe { left, right } => {
                 self.traverse_expr(body, *left, scope);
                 self.traverse_expr(body, *right, scope);
@@ -389,27 +411,32 @@ impl ExprScopes {
                     self.traverse_expr(body, *s, scope);
                 }
 
-                clauses.iter().for_each(|Clause { patterns, expr }| {
+                for clause in clauses.iter() {
                     let clause_scope = self.scopes.alloc(ScopeData {
                         parent: Some(scope),
                         entries: Vec::new(),
                     });
-                    for pat in patterns.iter() {
+                    for pat in clause.rows().flatten() {
                         self.add_bindings(body, clause_scope, pat);
                     }
-                    self.traverse_expr(body, *expr, clause_scope);
-                });
+                    // Guards can see the bindings of their clause's patterns.
+                    if let Some(guard) = clause.guard {
+                        self.traverse_expr(body, guard, clause_scope);
+                    }
+                    self.traverse_expr(body, clause.expr, clause_scope);
+                }
             }
             Expr::Lambda {
                 body: lam_body,
                 params,
+                ..
             } => {
                 let body_scope = self.scopes.alloc(ScopeData {
                     parent: Some(scope),
                     entries: Vec::new(),
                 });
-                for param in params.clone() {
-                    self.add_bindings(body, body_scope, &param);
+                for (param, _) in params {
+                    self.add_bindings(body, body_scope, param);
                 }
                 self.traverse_expr(body, *lam_body, body_scope);
             }
@@ -423,6 +450,12 @@ impl ExprScopes {
                     self.traverse_expr(body, *elem, scope);
                 }
             }
+            Expr::BitArray { segments } => {
+                for segment in segments.iter() {
+                    self.traverse_expr(body, segment.value, scope);
+                    self.traverse_segment_options(body, &segment.options, scope);
+                }
+            }
             _ => {}
         }
     }
@@ -434,6 +467,7 @@ impl ExprScopes {
                 Statement::Let {
                     pattern,
                     body: body_expr,
+                    ..
                 } => {
                     self.traverse_expr(body, *body_expr, scope);
                     scope = self.scopes.alloc(ScopeData {
@@ -451,7 +485,7 @@ impl ExprScopes {
                         parent: Some(scope),
                         entries: Vec::new(),
                     });
-                    for pattern in patterns {
+                    for (pattern, _) in patterns {
                         self.add_bindings(body, scope, pattern);
                     }
                 }
@@ -510,6 +544,27 @@ impl ExprScopes {
             Pattern::Concat { pattern } => {
                 self.add_bindings(body, scope, pattern);
             }
+            Pattern::BitArray { segments } => {
+                // Sizes may refer to variables bound by earlier segments,
+                // e.g. `<<len:8, data:bytes-size(len)>>`.
+                for segment in segments {
+                    self.add_bindings(body, scope, &segment.value);
+                    self.traverse_segment_options(body, &segment.options, scope);
+                }
+            }
+        }
+    }
+
+    fn traverse_segment_options(
+        &mut self,
+        body: &Body,
+        options: &[BitArrayOption],
+        scope: ScopeId,
+    ) {
+        for option in options {
+            if let BitArrayOption::Size(size) = option {
+                self.traverse_expr(body, *size, scope);
+            }
         }
     }
 }
@@ -9,16 +9,19 @@ use syntax::{
 use crate::{
     ide::NavigationTarget,
     impl_from,
-    ty::{FieldResolution, TyDatabase},
+    ty::{FieldResolution, InferenceResult, TyDatabase},
     DefDatabase, FileId, InFile,
 };
 
 use super::{
+    body::Body,
     hir::{
-        Adt, BuiltIn, Field, Function, Import, Local, Module, ModuleConstant, TypeAlias, Variant,
+        Adt, BuiltIn, Field, Function, Import, Local, Module, ModuleConstant, ModuleDef, TypeAlias,
+        Variant,
     },
     hir_def::ModuleDefId,
-    resolver::{resolver_for_toplevel, ResolveResult},
+    module::{Expr, ExprId},
+    resolver::{resolver_for_toplevel, ResolveResult, Resolver},
     source::HasSource,
     source_analyzer::SourceAnalyzer,
 };
@@ -184,13 +187,19 @@ impl From<FieldResolution> for Definition {
     fn from(value: FieldResolution) -> Self {
         match value {
             FieldResolution::Field(it) => it.into(),
-            FieldResolution::ModuleDef(def) => match def {
-                super::hir::ModuleDef::Function(it) => it.into(),
-                super::hir::ModuleDef::Variant(it) => it.into(),
-                super::hir::ModuleDef::Adt(it) => it.into(),
-                super::hir::ModuleDef::TypeAlias(it) => it.into(),
-                super::hir::ModuleDef::ModuleConstant(it) => it.into(),
-            },
+            FieldResolution::ModuleDef(def) => def.into(),
+        }
+    }
+}
+
+impl From<super::hir::ModuleDef> for Definition {
+    fn from(value: super::hir::ModuleDef) -> Self {
+        match value {
+            super::hir::ModuleDef::Function(it) => it.into(),
+            super::hir::ModuleDef::Variant(it) => it.into(),
+            super::hir::ModuleDef::Adt(it) => it.into(),
+            super::hir::ModuleDef::TypeAlias(it) => it.into(),
+            super::hir::ModuleDef::ModuleConstant(it) => it.into(),
         }
     }
 }
@@ -298,7 +307,7 @@ fn classify_type_name(sema: &Semantics, type_name: &ast::TypeName) -> Option<Def
 }
 
 fn classify_label(sema: &Semantics, label: &ast::Label) -> Option<Definition> {
-    let label_text = SmolStr::from(label.text()?);
+    let label_text = label.text()?;
 
     let parent = label.syntax().parent()?;
 
@@ -597,3 +606,22 @@ pub fn find_container(db: &dyn DefDatabase, node: InFile<&SyntaxNode>) -> Option
     }
     None
 }
+
+/// The module level definition `expr` refers to, if any.
+pub(crate) fn referenced_def(
+    body: &Body,
+    infer: &InferenceResult,
+    resolver: &dyn Fn(ExprId) -> Resolver,
+    expr: ExprId,
+) -> Option<ModuleDef> {
+    match &body[expr] {
+        Expr::Variable(name) | Expr::VariantLiteral { name } => {
+            resolver(expr).resolve_name(name)?.module_def()
+        }
+        Expr::FieldAccess { .. } => match infer.resolve_field(expr)? {
+            FieldResolution::ModuleDef(def) => Some(def),
+            FieldResolution::Field(_) => None,
+        },
+        _ => None,
+    }
+}
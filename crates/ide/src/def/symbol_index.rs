@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use smol_str::SmolStr;
+use syntax::{ast::AstNode, SyntaxNode, TextRange};
+
+use crate::FileId;
+
+use super::module::Attrs;
+use super::DefDatabase;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Function,
+    Adt,
+    Variant,
+    Field,
+    TypeAlias,
+    Constant,
+    Module,
+}
+
+/// A module level definition, as found by workspace symbol search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSymbol {
+    pub name: SmolStr,
+    pub kind: SymbolKind,
+    /// The type a variant belongs to.
+    pub container: Option<SmolStr>,
+    pub full_range: TextRange,
+    pub focus_range: TextRange,
+    pub is_deprecated: bool,
+}
+
+impl FileSymbol {
+    fn new(
+        name: &SmolStr,
+        kind: SymbolKind,
+        node: &SyntaxNode,
+        name_node: Option<&SyntaxNode>,
+        attrs: &Attrs,
+    ) -> Self {
+        let full_range = node.text_range();
+        Self {
+            name: name.clone(),
+            kind,
+            container: None,
+            full_range,
+            focus_range: name_node.map_or(full_range, |n| n.text_range()),
+            is_deprecated: attrs.deprecated.is_some(),
+        }
+    }
+}
+
+/// The definitions of a module. Their ranges come from the syntax tree, so any edit to the module
+/// indexes it again, but edits to other modules don't.
+pub(crate) fn module_symbols_query(db: &dyn DefDatabase, file_id: FileId) -> Arc<Vec<FileSymbol>> {
+    let root = db.parse(file_id).syntax_node();
+    let items = db.module_items(file_id);
+    let mut symbols = Vec::new();
+
+    for (_, func) in items.functions() {
+        let node = func.ast_ptr.to_node(&root);
+        let name = node.name().map(|n| n.syntax().clone());
+        symbols.push(FileSymbol::new(
+            &func.name,
+            SymbolKind::Function,
+            node.syntax(),
+            name.as_ref(),
+            &func.attrs,
+        ));
+    }
+    for (_, adt) in items.adts() {
+        let node = adt.ast_ptr.to_node(&root);
+        let name = node.name().map(|n| n.syntax().clone());
+        symbols.push(FileSymbol::new(
+            &adt.name,
+            SymbolKind::Adt,
+            node.syntax(),
+            name.as_ref(),
+            &adt.attrs,
+        ));
+        // Variants are deprecated along with their type.
+        for variant in adt.variants.clone() {
+            let variant = &items[variant];
+            let node = variant.ast_ptr.to_node(&root);
+            let name = node.name().map(|n| n.syntax().clone());
+            symbols.push(FileSymbol {
+                container: Some(adt.name.clone()),
+                ..FileSymbol::new(
+                    &variant.name,
+                    SymbolKind::Variant,
+                    node.syntax(),
+                    name.as_ref(),
+                    &adt.attrs,
+                )
+            });
+        }
+    }
+    for (_, alias) in items.type_alias() {
+        let node = alias.ast_ptr.to_node(&root);
+        let name = node.name().map(|n| n.syntax().clone());
+        symbols.push(FileSymbol::new(
+            &alias.name,
+            SymbolKind::TypeAlias,
+            node.syntax(),
+            name.as_ref(),
+            &alias.attrs,
+        ));
+    }
+    for (_, constant) in items.constants() {
+        let node = constant.ast_ptr.to_node(&root);
+        let name = node.name().map(|n| n.syntax().clone());
+        symbols.push(FileSymbol::new(
+            &constant.name,
+            SymbolKind::Constant,
+            node.syntax(),
+            name.as_ref(),
+            &constant.attrs,
+        ));
+    }
+
+    Arc::new(symbols)
+}
@@ -1,3 +1,4 @@
+use crate::ty::MAX_MISSING;
 use crate::FileRange;
 use core::fmt;
 use syntax::{ErrorKind as SynErrorKind, TextRange};
@@ -9,7 +10,7 @@ pub struct Diagnostic {
     pub notes: Vec<(FileRange, String)>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DiagnosticKind {
     // Syntax.
     SyntaxError(SynErrorKind),
@@ -18,7 +19,33 @@ pub enum DiagnosticKind {
     // Name resolution.
     InactiveTarget,
     DuplicatedParam,
+    UnresolvedVariable { name: String },
+    UnresolvedConstructor { name: String },
+    UnresolvedType { name: String },
+    UnresolvedModule { name: String },
+    UnresolvedModuleMember { module: String, name: String },
+    UnresolvedField { constructor: String, label: String },
+    CyclicTypeAlias { name: String },
+    OpaqueTypeAccess { name: String },
+    MissingTargetImplementation { name: String, target: String },
+    Deprecated { name: String, message: String },
+    WrongArgumentCount { expected: usize, found: usize },
+    UnknownLabel { label: String },
+    DuplicateLabel { label: String },
+    PositionalAfterLabelled,
+    UnlabelledUpdateField,
     // Liveness.
+
+    // Type inference.
+    TypeMismatch { expected: String, actual: String },
+
+    // Pattern checking.
+    InexhaustiveCase { missing: Vec<String> },
+    InexhaustiveLet { missing: Vec<String> },
+    UnreachableClause,
+
+    // Unfinished code.
+    Todo,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -48,6 +75,26 @@ impl Diagnostic {
             DiagnosticKind::SyntaxError(_) => "syntax_error",
             DiagnosticKind::InactiveTarget => "inactive_target",
             DiagnosticKind::DuplicatedParam => "duplicated_param",
+            DiagnosticKind::UnresolvedVariable { .. } => "unresolved_variable",
+            DiagnosticKind::UnresolvedConstructor { .. } => "unresolved_constructor",
+            DiagnosticKind::UnresolvedType { .. } => "unresolved_type",
+            DiagnosticKind::UnresolvedModule { .. } => "unresolved_module",
+            DiagnosticKind::UnresolvedModuleMember { .. } => "unresolved_module_member",
+            DiagnosticKind::UnresolvedField { .. } => "unresolved_field",
+            DiagnosticKind::CyclicTypeAlias { .. } => "cyclic_type_alias",
+            DiagnosticKind::OpaqueTypeAccess { .. } => "opaque_type_access",
+            DiagnosticKind::MissingTargetImplementation { .. } => "missing_target_implementation",
+            DiagnosticKind::Deprecated { .. } => "deprecated",
+            DiagnosticKind::WrongArgumentCount { .. } => "wrong_argument_count",
+            DiagnosticKind::UnknownLabel { .. } => "unknown_label",
+            DiagnosticKind::DuplicateLabel { .. } => "duplicate_label",
+            DiagnosticKind::PositionalAfterLabelled => "positional_after_labelled",
+            DiagnosticKind::UnlabelledUpdateField => "unlabelled_update_field",
+            DiagnosticKind::TypeMismatch { .. } => "type_mismatch",
+            DiagnosticKind::InexhaustiveCase { .. } => "inexhaustive_case",
+            DiagnosticKind::InexhaustiveLet { .. } => "inexhaustive_let",
+            DiagnosticKind::UnreachableClause => "unreachable_clause",
+            DiagnosticKind::Todo => "todo",
         }
     }
 
@@ -56,14 +103,89 @@ impl Diagnostic {
             DiagnosticKind::SyntaxError(_) => Severity::Error,
             DiagnosticKind::InactiveTarget => Severity::Info,
             DiagnosticKind::DuplicatedParam => Severity::Error,
+            DiagnosticKind::UnresolvedVariable { .. }
+            | DiagnosticKind::UnresolvedConstructor { .. }
+            | DiagnosticKind::UnresolvedType { .. }
+            | DiagnosticKind::UnresolvedModule { .. }
+            | DiagnosticKind::UnresolvedModuleMember { .. }
+            | DiagnosticKind::UnresolvedField { .. } => Severity::Error,
+            DiagnosticKind::CyclicTypeAlias { .. } => Severity::Error,
+            DiagnosticKind::OpaqueTypeAccess { .. } => Severity::Error,
+            DiagnosticKind::MissingTargetImplementation { .. } => Severity::Error,
+            DiagnosticKind::Deprecated { .. } => Severity::Warning,
+            DiagnosticKind::WrongArgumentCount { .. }
+            | DiagnosticKind::UnknownLabel { .. }
+            | DiagnosticKind::DuplicateLabel { .. }
+            | DiagnosticKind::PositionalAfterLabelled
+            | DiagnosticKind::UnlabelledUpdateField => Severity::Error,
+            DiagnosticKind::TypeMismatch { .. } => Severity::Error,
+            DiagnosticKind::InexhaustiveCase { .. } => Severity::Error,
+            DiagnosticKind::InexhaustiveLet { .. } => Severity::Error,
+            DiagnosticKind::UnreachableClause => Severity::Warning,
+            DiagnosticKind::Todo => Severity::Warning,
         }
     }
 
     pub fn message(&self) -> String {
-        match self.kind {
+        match &self.kind {
             DiagnosticKind::SyntaxError(kind) => return kind.to_string(),
             DiagnosticKind::InactiveTarget => "Inactive Target",
             DiagnosticKind::DuplicatedParam => "Duplicated Param",
+            DiagnosticKind::UnresolvedVariable { name } => {
+                return format!("Unknown variable `{name}`")
+            }
+            DiagnosticKind::UnresolvedConstructor { name } => {
+                return format!("Unknown constructor `{name}`")
+            }
+            DiagnosticKind::UnresolvedType { name } => return format!("Unknown type `{name}`"),
+            DiagnosticKind::UnresolvedModule { name } => return format!("Unknown module `{name}`"),
+            DiagnosticKind::UnresolvedModuleMember { module, name } => {
+                return format!("Module `{module}` has no member `{name}`")
+            }
+            DiagnosticKind::UnresolvedField { constructor, label } => {
+                return format!("Constructor `{constructor}` has no field `{label}`")
+            }
+            DiagnosticKind::CyclicTypeAlias { name } => {
+                return format!("Type alias `{name}` refers to itself")
+            }
+            DiagnosticKind::OpaqueTypeAccess { name } => {
+                return format!("Type `{name}` is opaque outside of its module")
+            }
+            DiagnosticKind::MissingTargetImplementation { name, target } => {
+                return format!("`{name}` has no implementation for the {target} target")
+            }
+            DiagnosticKind::Deprecated { name, message } if message.is_empty() => {
+                return format!("`{name}` is deprecated")
+            }
+            DiagnosticKind::Deprecated { name, message } => {
+                return format!("`{name}` is deprecated: {message}")
+            }
+            DiagnosticKind::WrongArgumentCount { expected, found } => {
+                let s = if *expected == 1 { "" } else { "s" };
+                return format!("Expected {expected} argument{s}, found {found}");
+            }
+            DiagnosticKind::UnknownLabel { label } => return format!("Unknown label `{label}`"),
+            DiagnosticKind::DuplicateLabel { label } => {
+                return format!("Argument `{label}` is given more than once")
+            }
+            DiagnosticKind::PositionalAfterLabelled => {
+                "Positional arguments must come before labelled ones"
+            }
+            DiagnosticKind::UnlabelledUpdateField => "Fields of a record update must be labelled",
+            DiagnosticKind::TypeMismatch { expected, actual } => {
+                return format!("Type mismatch: expected {expected}, found {actual}")
+            }
+            DiagnosticKind::InexhaustiveCase { missing } => {
+                return format!("Inexhaustive patterns, missing: {}", missing_text(missing))
+            }
+            DiagnosticKind::InexhaustiveLet { missing } => {
+                return format!(
+                "This pattern does not match every value, use `let assert` instead. Missing: {}",
+                missing_text(missing)
+            )
+            }
+            DiagnosticKind::UnreachableClause => "Unreachable case clause",
+            DiagnosticKind::Todo => "This code is incomplete",
         }
         .into()
     }
@@ -102,6 +224,15 @@ impl Diagnostic {
     }
 }
 
+/// Lists missing patterns, with an ellipsis when there are more than are reported.
+fn missing_text(missing: &[String]) -> String {
+    let mut text = missing[..missing.len().min(MAX_MISSING)].join(" | ");
+    if missing.len() > MAX_MISSING {
+        text.push_str(" | …");
+    }
+    text
+}
+
 impl From<syntax::Error> for Diagnostic {
     fn from(err: syntax::Error) -> Self {
         Self::new(err.range, DiagnosticKind::SyntaxError(err.kind))
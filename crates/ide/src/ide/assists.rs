@@ -0,0 +1,146 @@
+//! Code actions which rewrite the code around the cursor or selection.
+mod add_missing_clauses;
+
+use std::iter;
+
+use syntax::{ast::AstNode, GleamLanguage, SyntaxElement, SyntaxNode, TextRange};
+
+use crate::{def::Semantics, ty::TyDatabase, FileRange, TextEdit, WorkspaceEdit};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assist {
+    pub id: &'static str,
+    pub label: String,
+    pub kind: AssistKind,
+    pub edits: WorkspaceEdit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssistKind {
+    QuickFix,
+    RefactorRewrite,
+}
+
+type Handler = fn(&mut AssistsCtx<'_>) -> Option<()>;
+
+const HANDLERS: &[Handler] = &[add_missing_clauses::add_missing_clauses];
+
+pub(crate) fn code_actions(db: &dyn TyDatabase, frange: FileRange) -> Vec<Assist> {
+    let mut ctx = AssistsCtx::new(db, frange);
+    for handler in HANDLERS {
+        handler(&mut ctx);
+    }
+    ctx.res
+}
+
+pub(crate) struct AssistsCtx<'db> {
+    pub(crate) sema: Semantics<'db>,
+    pub(crate) frange: FileRange,
+    covering_element: SyntaxElement,
+    res: Vec<Assist>,
+}
+
+impl<'db> AssistsCtx<'db> {
+    fn new(db: &'db dyn TyDatabase, frange: FileRange) -> Self {
+        let sema = Semantics::new(db);
+        let root = sema.parse(frange.file_id).syntax().clone();
+        let range = frange
+            .range
+            .intersect(root.text_range())
+            .unwrap_or_else(|| TextRange::empty(root.text_range().end()));
+        let covering_element = root.covering_element(range);
+        Self {
+            sema,
+            frange,
+            covering_element,
+            res: Vec::new(),
+        }
+    }
+
+    pub(crate) fn db(&self) -> &'db dyn TyDatabase {
+        self.sema.db
+    }
+
+    /// The innermost node of type `N` containing the whole range.
+    pub(crate) fn covering_node<N: AstNode<Language = GleamLanguage>>(&self) -> Option<N> {
+        let node = match &self.covering_element {
+            SyntaxElement::Node(node) => node.clone(),
+            SyntaxElement::Token(token) => token.parent()?,
+        };
+        node.ancestors().find_map(N::cast)
+    }
+
+    pub(crate) fn add(
+        &mut self,
+        id: &'static str,
+        label: impl Into<String>,
+        kind: AssistKind,
+        edits: Vec<TextEdit>,
+    ) {
+        self.res.push(Assist {
+            id,
+            label: label.into(),
+            kind,
+            edits: WorkspaceEdit {
+                content_edits: iter::once((self.frange.file_id, edits)).collect(),
+            },
+        });
+    }
+}
+
+/// The whitespace a line starts with, used to indent inserted lines like their neighbours.
+pub(crate) fn indent_of(node: &SyntaxNode) -> String {
+    let text = node.ancestors().last().unwrap().to_string();
+    let start = usize::from(node.text_range().start());
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    text[line_start..start]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect()
+}
+
+/// One level of indentation, taken from how far `node` is indented relative to the first less
+/// indented line around it. Falls back to the two spaces `gleam format` uses.
+pub(crate) fn indent_unit(node: &SyntaxNode) -> String {
+    let indent = indent_of(node);
+    node.ancestors()
+        .skip(1)
+        .map(|it| indent_of(&it))
+        .find(|outer| outer.len() < indent.len() && indent.starts_with(outer.as_str()))
+        .map_or_else(|| "  ".into(), |outer| indent[outer.len()..].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::Expect;
+
+    use super::{AssistsCtx, Handler};
+    use crate::{tests::TestDB, SourceDatabase};
+
+    #[track_caller]
+    pub(crate) fn check(handler: Handler, fixture: &str, expect: Expect) {
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        let frange = f.unwrap_single_range_marker();
+        let mut ctx = AssistsCtx::new(&db, frange);
+        assert!(handler(&mut ctx).is_some(), "No assist");
+        assert_eq!(ctx.res.len(), 1, "Ambiguous assists");
+
+        let assist = ctx.res.pop().unwrap();
+        let mut src = db.file_content(frange.file_id).to_string();
+        let mut edits = assist.edits.content_edits[&frange.file_id].clone();
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.delete.start()));
+        for edit in edits {
+            edit.apply(&mut src);
+        }
+        expect.assert_eq(&format!("{}\n{}", assist.label, src));
+    }
+
+    #[track_caller]
+    pub(crate) fn check_no(handler: Handler, fixture: &str) {
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        let frange = f.unwrap_single_range_marker();
+        let mut ctx = AssistsCtx::new(&db, frange);
+        handler(&mut ctx);
+        assert!(ctx.res.is_empty(), "Unexpected assist: {:?}", ctx.res);
+    }
+}
@@ -0,0 +1,412 @@
+//! Fill in the variants a `case` over a custom type doesn't handle yet.
+//!
+//! ```gleam
+//! case pet {
+//!   Cat -> "meow"
+//! }
+//! ```
+//! =>
+//! ```gleam
+//! case pet {
+//!   Cat -> "meow"
+//!   Dog(name: _) -> todo
+//! }
+//! ```
+use smol_str::SmolStr;
+use syntax::ast::{self, AstNode};
+use syntax::{TextRange, T};
+
+use super::{indent_of, indent_unit, AssistKind, AssistsCtx};
+use crate::def::body::Body;
+use crate::def::find_container;
+use crate::def::hir::{Adt, Variant};
+use crate::def::hir_def::ModuleDefId;
+use crate::def::module::{Clause, Expr, Pattern, PatternId};
+use crate::def::resolver::{resolver_for_toplevel, ResolveResult};
+use crate::ty::{missing_patterns, Ty, MAX_MISSING};
+use crate::{InFile, TextEdit};
+
+pub(super) fn add_missing_clauses(ctx: &mut AssistsCtx<'_>) -> Option<()> {
+    let db = ctx.db();
+    let file_id = ctx.frange.file_id;
+    let case_node = ctx.covering_node::<ast::Case>()?;
+
+    let ModuleDefId::FunctionId(fn_id) =
+        find_container(db.upcast(), InFile::new(file_id, case_node.syntax()))?
+    else {
+        return None;
+    };
+    let (body, source_map) = db.body_with_source_map(fn_id);
+    let case_expr = ast::Expr::Case(case_node.clone());
+    let case_id = source_map.expr_for_node(InFile::new(file_id, &case_expr))?;
+    let Expr::Case { subjects, clauses } = &body[case_id] else {
+        return None;
+    };
+
+    // A case without clauses isn't checked, but obviously misses everything.
+    let missing =
+        missing_patterns(db, fn_id, case_id).or_else(|| clauses.is_empty().then(Vec::new))?;
+
+    // Whole variants which aren't mentioned at all come first, since those are
+    // what users are usually after. The example values cover everything else,
+    // like `Circle(_)` when only `Circle(1)` is handled.
+    let mut new_patterns = Vec::new();
+    let mut unmentioned = Vec::new();
+    if let [subject] = subjects[..] {
+        if let Ty::Adt { adt_id, .. } = db.infer_function(fn_id).ty_for_expr(subject).unalias() {
+            let mut matched = Vec::new();
+            for row in clauses.iter().flat_map(Clause::rows) {
+                if let Some(&pat) = row.first() {
+                    collect_variant_names(&body, pat, &mut matched);
+                }
+            }
+            let adt = Adt { id: *adt_id };
+            for variant in adt.variants(db.upcast()) {
+                let name = variant.name(db.upcast());
+                if !matched.contains(&name) {
+                    new_patterns.push(render_variant(ctx, variant));
+                    unmentioned.push(variant);
+                }
+            }
+        }
+    }
+    // Examples of an unmentioned variant are covered by its clause already.
+    new_patterns.extend(
+        missing
+            .into_iter()
+            .take(MAX_MISSING)
+            .filter(|pat| pat.variant.map_or(true, |it| !unmentioned.contains(&it)))
+            .map(|pat| pat.text),
+    );
+    if new_patterns.is_empty() {
+        return None;
+    }
+
+    let mut trailing = String::new();
+    let (insert_at, indent) = match case_node.clauses().last() {
+        Some(clause) => (
+            clause.syntax().text_range().end(),
+            indent_of(clause.syntax()),
+        ),
+        None => {
+            let l_curly = case_node
+                .syntax()
+                .children_with_tokens()
+                .find(|it| it.kind() == T!["{"])?;
+            let case_indent = indent_of(case_node.syntax());
+            // `case x {}` needs the closing brace moved onto its own line.
+            if l_curly
+                .next_sibling_or_token()
+                .map_or(false, |it| it.kind() == T!["}"])
+            {
+                trailing = format!("\n{case_indent}");
+            }
+            let indent = format!("{case_indent}{}", indent_unit(case_node.syntax()));
+            (l_curly.text_range().end(), indent)
+        }
+    };
+    let mut insert: String = new_patterns
+        .iter()
+        .map(|pat| format!("\n{indent}{pat} -> todo"))
+        .collect();
+    insert.push_str(&trailing);
+
+    ctx.add(
+        "add_missing_clauses",
+        "Add missing case clauses",
+        AssistKind::QuickFix,
+        vec![TextEdit {
+            delete: TextRange::empty(insert_at),
+            insert: insert.into(),
+        }],
+    );
+    Some(())
+}
+
+/// Names of the variants a clause pattern matches at the top level.
+fn collect_variant_names(body: &Body, pat: PatternId, names: &mut Vec<SmolStr>) {
+    match &body[pat] {
+        Pattern::VariantRef { name, .. } => names.push(name.clone()),
+        Pattern::AlternativePattern { patterns } => {
+            for &pat in patterns {
+                collect_variant_names(body, pat, names);
+            }
+        }
+        Pattern::AsPattern { pattern, .. } => collect_variant_names(body, *pattern, names),
+        _ => {}
+    }
+}
+
+fn render_variant(ctx: &AssistsCtx<'_>, variant: Variant) -> String {
+    let db = ctx.db().upcast();
+    let file_id = ctx.frange.file_id;
+    let name = variant.name(db);
+
+    let resolver = resolver_for_toplevel(db, file_id);
+    let mut rendered = match resolver.resolve_name(&name) {
+        Some(ResolveResult::Variant(it)) if it == variant => name.to_string(),
+        _ => {
+            let target = variant.module(db).id;
+            let qualifier = db
+                .module_items(file_id)
+                .module_imports()
+                .map(|(_, import)| import.as_name.clone().unwrap_or(import.accessor.clone()))
+                .find(|accessor| resolver.resolve_module(accessor) == Some(target));
+            match qualifier {
+                Some(qualifier) if target != file_id => format!("{qualifier}.{name}"),
+                _ => name.to_string(),
+            }
+        }
+    };
+
+    let fields = variant.fields(db);
+    if !fields.is_empty() {
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|field| match field.label(db) {
+                Some(label) => format!("{label}: _"),
+                None => "_".into(),
+            })
+            .collect();
+        rendered.push('(');
+        rendered.push_str(&fields.join(", "));
+        rendered.push(')');
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::super::tests::{check, check_no};
+
+    #[track_caller]
+    fn check_assist(fixture: &str, expect: expect_test::Expect) {
+        check(super::add_missing_clauses, fixture, expect);
+    }
+
+    #[test]
+    fn missing_variants() {
+        check_assist(
+            r#"
+type Pet {
+    Cat
+    Dog(name: String, age: Int)
+    Fish
+}
+fn main(pet: Pet) {
+    case $0pet {
+        Cat -> 1
+    }
+}"#,
+            expect![[r#"
+                Add missing case clauses
+                type Pet {
+                    Cat
+                    Dog(name: String, age: Int)
+                    Fish
+                }
+                fn main(pet: Pet) {
+                    case pet {
+                        Cat -> 1
+                        Dog(name: _, age: _) -> todo
+                        Fish -> todo
+                    }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn empty_case() {
+        check_assist(
+            r#"
+type Bool2 {
+    Yes
+    No
+}
+fn main(b: Bool2) {
+    case b {$0}
+}"#,
+            expect![[r#"
+                Add missing case clauses
+                type Bool2 {
+                    Yes
+                    No
+                }
+                fn main(b: Bool2) {
+                    case b {
+                        Yes -> todo
+                        No -> todo
+                    }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn empty_case_follows_indentation() {
+        check_assist(
+            r#"
+type Answer {
+  Yes
+  No
+}
+fn main(a: Answer) {
+  let x = case a {$0}
+  x
+}"#,
+            expect![[r#"
+                Add missing case clauses
+                type Answer {
+                  Yes
+                  No
+                }
+                fn main(a: Answer) {
+                  let x = case a {
+                    Yes -> todo
+                    No -> todo
+                  }
+                  x
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn alternatives_and_nested_witnesses() {
+        check_assist(
+            r#"
+type Shape {
+    Circle(Int)
+    Square(Int)
+    Point
+}
+fn main(s: Shape) {
+    case s {
+        Circle(1) | Point -> 1$0
+        Square(_) -> 2
+    }
+}"#,
+            expect![[r#"
+                Add missing case clauses
+                type Shape {
+                    Circle(Int)
+                    Square(Int)
+                    Point
+                }
+                fn main(s: Shape) {
+                    case s {
+                        Circle(1) | Point -> 1
+                        Square(_) -> 2
+                        Circle(_) -> todo
+                    }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn unmentioned_variants_and_witnesses() {
+        check_assist(
+            r#"
+type Shape {
+    Circle(Int)
+    Square(Int)
+    Point
+}
+fn main(s: Shape) {
+    case $0s {
+        Circle(1) -> 1
+    }
+}"#,
+            expect![[r#"
+                Add missing case clauses
+                type Shape {
+                    Circle(Int)
+                    Square(Int)
+                    Point
+                }
+                fn main(s: Shape) {
+                    case s {
+                        Circle(1) -> 1
+                        Square(_) -> todo
+                        Point -> todo
+                        Circle(_) -> todo
+                    }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn qualified_variants() {
+        check_assist(
+            r#"
+#- animal.gleam
+pub type Animal {
+    Cat
+    Dog
+}
+#- main.gleam
+import animal
+fn main(a: animal.Animal) {
+    case $0a {
+        animal.Cat -> 1
+    }
+}"#,
+            expect![[r#"
+                Add missing case clauses
+                import animal
+                fn main(a: animal.Animal) {
+                    case a {
+                        animal.Cat -> 1
+                        animal.Dog -> todo
+                    }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn qualified_unmentioned_variants_and_witnesses() {
+        check_assist(
+            r#"
+#- shapes.gleam
+pub type Shape {
+    Circle(Int)
+    Square(Int)
+}
+#- main.gleam
+import shapes as s
+fn main(shape: s.Shape) {
+    case $0shape {
+        s.Circle(1) -> 1
+    }
+}"#,
+            expect![[r#"
+                Add missing case clauses
+                import shapes as s
+                fn main(shape: s.Shape) {
+                    case shape {
+                        s.Circle(1) -> 1
+                        s.Square(_) -> todo
+                        s.Circle(_) -> todo
+                    }
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn exhaustive() {
+        check_no(
+            super::add_missing_clauses,
+            r#"
+type Pet {
+    Cat
+    Dog
+}
+fn main(pet: Pet) {
+    case $0pet {
+        Cat -> 1
+        Dog -> 2
+    }
+}"#,
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use smol_str::SmolStr;
+use syntax::ast::AstNode;
+use syntax::best_token_at_offset;
+use syntax::{NodeOrToken, TextRange};
+
+use super::NavigationTarget;
+
+use crate::def::hir::{Function, ModuleDef};
+use crate::def::hir_def::ModuleDefId;
+use crate::def::semantics::{self, referenced_def, Definition};
+use crate::def::{find_container, resolver_for_expr, SearchScope, Semantics};
+use crate::ty::TyDatabase;
+use crate::{FilePos, InFile};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallHierarchyItem {
+    pub name: SmolStr,
+    /// The module the function is defined in.
+    pub module: SmolStr,
+    pub nav: NavigationTarget,
+    pub is_deprecated: bool,
+}
+
+impl CallHierarchyItem {
+    fn new(db: &dyn TyDatabase, func: Function) -> Option<Self> {
+        Some(Self {
+            name: func.name(db.upcast()),
+            module: func.module(db.upcast()).name(db.upcast()),
+            nav: Definition::Function(func).to_nav(db)?,
+            is_deprecated: func.attrs(db.upcast()).deprecated.is_some(),
+        })
+    }
+}
+
+/// A function calling the item, `ranges` are the call sites in the caller's file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingCall {
+    pub from: CallHierarchyItem,
+    pub ranges: Vec<TextRange>,
+}
+
+/// A function the item calls, `ranges` are the call sites in the item's file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutgoingCall {
+    pub to: CallHierarchyItem,
+    pub ranges: Vec<TextRange>,
+}
+
+pub(crate) fn prepare_call_hierarchy(
+    db: &dyn TyDatabase,
+    fpos: FilePos,
+) -> Option<CallHierarchyItem> {
+    let func = function_at(db, fpos)?;
+    CallHierarchyItem::new(db, func)
+}
+
+/// Every function referring to the one at `fpos`, passing it along counts as calling it.
+pub(crate) fn incoming_calls(db: &dyn TyDatabase, fpos: FilePos) -> Option<Vec<IncomingCall>> {
+    let sema = Semantics::new(db);
+    let func = function_at(db, fpos)?;
+    let def_nav = Definition::Function(func).to_nav(db)?;
+
+    let mut calls: Vec<(Function, Vec<TextRange>)> = Vec::new();
+    let usages = Definition::Function(func)
+        .usages(&sema)
+        .in_scope(&SearchScope::package_graph(db.upcast()))
+        .all();
+    for (file_id, ranges) in usages {
+        let root = sema.parse(file_id);
+        for range in ranges {
+            if file_id == def_nav.file_id && range == def_nav.focus_range {
+                continue;
+            }
+            let node = match root.syntax().covering_element(range) {
+                NodeOrToken::Node(node) => node,
+                NodeOrToken::Token(token) => match token.parent() {
+                    Some(node) => node,
+                    None => continue,
+                },
+            };
+            // References outside of functions, like imports, don't call anything.
+            let Some(ModuleDefId::FunctionId(caller)) =
+                find_container(db.upcast(), InFile::new(file_id, &node))
+            else {
+                continue;
+            };
+            let caller = Function::from(caller);
+            match calls.iter_mut().find(|(func, _)| *func == caller) {
+                Some((_, ranges)) => ranges.push(range),
+                None => calls.push((caller, vec![range])),
+            }
+        }
+    }
+
+    let mut calls = calls
+        .into_iter()
+        .filter_map(|(caller, mut ranges)| {
+            ranges.sort_by_key(|range| range.start());
+            Some(IncomingCall {
+                from: CallHierarchyItem::new(db, caller)?,
+                ranges,
+            })
+        })
+        .collect::<Vec<_>>();
+    calls.sort_by_key(|call| (call.from.nav.file_id, call.from.nav.full_range.start()));
+    Some(calls)
+}
+
+/// Every function the body of the one at `fpos` refers to, in order of their first reference.
+/// Like for incoming calls, passing a function along counts as calling it.
+pub(crate) fn outgoing_calls(db: &dyn TyDatabase, fpos: FilePos) -> Option<Vec<OutgoingCall>> {
+    let func = function_at(db, fpos)?;
+    let infer = db.infer_function(func.id);
+    let (body, source_map) = db.body_with_source_map(func.id);
+    let resolver = |expr| resolver_for_expr(db.upcast(), func.id, expr);
+
+    let mut calls: Vec<(Function, Vec<TextRange>)> = Vec::new();
+    for (callee, _) in body.exprs() {
+        let Some(ModuleDef::Function(callee_fn)) = referenced_def(&body, &infer, &resolver, callee)
+        else {
+            continue;
+        };
+        let Some(src) = source_map.node_for_expr(callee) else {
+            continue;
+        };
+        let range = src.value.text_range();
+        match calls.iter_mut().find(|(func, _)| *func == callee_fn) {
+            Some((_, ranges)) => ranges.push(range),
+            None => calls.push((callee_fn, vec![range])),
+        }
+    }
+
+    Some(
+        calls
+            .into_iter()
+            .filter_map(|(callee, mut ranges)| {
+                ranges.sort_by_key(|range| range.start());
+                Some(OutgoingCall {
+                    to: CallHierarchyItem::new(db, callee)?,
+                    ranges,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn function_at(db: &dyn TyDatabase, FilePos { file_id, pos }: FilePos) -> Option<Function> {
+    let sema = Semantics::new(db);
+    let parse = sema.parse(file_id);
+    let tok = best_token_at_offset(parse.syntax(), pos)?;
+    match semantics::classify_node(&sema, &tok.parent()?)? {
+        Definition::Function(func) => Some(func),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::base::SourceDatabase;
+    use crate::tests::TestDB;
+
+    use super::CallHierarchyItem;
+
+    fn render_item(item: &CallHierarchyItem) -> String {
+        format!(
+            "{}.{} {:?} {:?}",
+            item.module, item.name, item.nav.file_id, item.nav.focus_range
+        )
+    }
+
+    #[track_caller]
+    fn check_incoming(fixture: &str, expect: Expect) {
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        let mut got = String::new();
+        for call in super::incoming_calls(&db, f[0]).expect("No function") {
+            let src = db.file_content(call.from.nav.file_id);
+            let ranges = call
+                .ranges
+                .iter()
+                .map(|&range| format!("{range:?} {}", &src[range]))
+                .collect::<Vec<_>>();
+            got += &format!("{} <- {}\n", render_item(&call.from), ranges.join(", "));
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[track_caller]
+    fn check_outgoing(fixture: &str, expect: Expect) {
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        let src = db.file_content(f[0].file_id);
+        let mut got = String::new();
+        for call in super::outgoing_calls(&db, f[0]).expect("No function") {
+            let ranges = call
+                .ranges
+                .iter()
+                .map(|&range| format!("{range:?} {}", &src[range]))
+                .collect::<Vec<_>>();
+            got += &format!("{} <- {}\n", render_item(&call.to), ranges.join(", "));
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[test]
+    fn prepare() {
+        let (db, f) = TestDB::from_fixture("fn ma$0in() { 1 }").unwrap();
+        let item = super::prepare_call_hierarchy(&db, f[0]).unwrap();
+        expect!["test.main FileId(0) 3..7"].assert_eq(&render_item(&item));
+
+        let (db, f) = TestDB::from_fixture("fn main() { $01 }").unwrap();
+        assert_eq!(super::prepare_call_hierarchy(&db, f[0]), None);
+    }
+
+    #[test]
+    fn incoming() {
+        check_incoming(
+            r#"
+#- util.gleam
+pub fn dou$0ble(x) { x * 2 }
+pub fn quadruple(x) { double(double(x)) }
+#- main.gleam
+import util.{double}
+fn main() {
+  let f = util.double
+  1 |> double
+}
+const unused = 1
+"#,
+            expect![[r#"
+                util.quadruple FileId(0) 34..43 <- 49..55 double, 56..62 double
+                main.main FileId(1) 24..28 <- 48..54 double, 62..68 double
+            "#]],
+        );
+    }
+
+    #[test]
+    fn outgoing() {
+        check_outgoing(
+            r#"
+#- util.gleam
+pub fn double(x) { x * 2 }
+pub fn add(x, y) { x + y }
+#- main.gleam
+import util
+type Box { Box(Int) }
+fn ma$0in() {
+  let x = util.double(1)
+  x
+  |> util.add(2)
+  |> util.double
+  |> helper
+  |> Box
+  helper(x)
+}
+fn helper(x) { x }
+"#,
+            expect![[r#"
+                util.double FileId(0) 7..13 <- 56..67 util.double, 97..108 util.double
+                util.add FileId(0) 34..37 <- 80..88 util.add
+                main.helper FileId(1) 147..153 <- 114..120 helper, 132..138 helper
+            "#]],
+        );
+    }
+
+    #[test]
+    fn incoming_as_argument_and_use() {
+        check_incoming(
+            r#"
+fn twice(f, x) { f(f(x)) }
+fn wrap(f) { f() }
+fn in$0c(x) { x + 1 }
+fn main() {
+  twice(inc, 1)
+  use <- wrap
+  inc
+}
+"#,
+            expect![[r#"
+                test.main FileId(0) 69..73 <- 86..89 inc, 110..113 inc
+            "#]],
+        );
+        check_incoming(
+            r#"
+fn twice(f, x) { f(f(x)) }
+fn wr$0ap(f) { f() }
+fn inc(x) { x + 1 }
+fn main() {
+  twice(inc, 1)
+  use <- wrap
+  inc
+}
+"#,
+            expect![[r#"
+                test.main FileId(0) 69..73 <- 103..107 wrap
+            "#]],
+        );
+    }
+
+    #[test]
+    fn outgoing_as_argument_and_use() {
+        check_outgoing(
+            r#"
+fn twice(f, x) { f(f(x)) }
+fn wrap(f) { f() }
+fn inc(x) { x + 1 }
+fn ma$0in() {
+  twice(inc, 1)
+  use <- wrap
+  inc
+}
+"#,
+            expect![[r#"
+                test.twice FileId(0) 3..8 <- 80..85 twice
+                test.inc FileId(0) 49..52 <- 86..89 inc, 110..113 inc
+                test.wrap FileId(0) 30..34 <- 103..107 wrap
+            "#]],
+        );
+    }
+}
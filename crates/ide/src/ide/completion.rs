@@ -3,7 +3,7 @@ mod render;
 use crate::{
     def::{
         find_container,
-        hir::{Adt, Module, Package},
+        hir::{Adt, Module, ModuleDef, Package},
         hir_def::ModuleDefId,
         module::Visibility,
         resolver::{resolver_for_toplevel, ResolveResult},
@@ -13,6 +13,7 @@ use crate::{
     FilePos, InFile,
 };
 use smol_str::SmolStr;
+use std::collections::HashMap;
 use std::ops::Deref;
 use syntax::{
     ast::{self, AstNode, SourceFile},
@@ -41,6 +42,8 @@ pub struct CompletionItem {
     pub documentation: Option<String>,
     /// Is this a snippet.
     pub is_snippet: bool,
+    /// Whether the item is marked `@deprecated`.
+    pub is_deprecated: bool,
 }
 
 impl CompletionRelevance {
@@ -113,6 +116,7 @@ pub enum CompletionRelevanceTypeMatch {
 pub struct CompletionContext<'db> {
     db: &'db dyn TyDatabase,
     sema: Semantics<'db>,
+    module: Module,
     package: Package,
     is_top_level: bool,
     source_range: TextRange,
@@ -142,6 +146,7 @@ impl<'db> CompletionContext<'db> {
             sema,
             is_top_level: false,
             source_range: TextRange::default(),
+            module,
             package: module.package(db.upcast()),
             expr_ptr: None,
             tok,
@@ -237,9 +242,17 @@ fn complete_labels(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) -
         .analyze(&ctx.tok.parent()?)?
         .type_of_expr(&expr_call.func()?)?;
 
-    if let Ty::Function { params, return_: _ } = ty {
+    // Each label may only be given once, also in record updates.
+    let used: Vec<SmolStr> = expr_call
+        .arguments()
+        .into_iter()
+        .flat_map(|args| args.args())
+        .filter_map(|arg| arg.label()?.text())
+        .collect();
+
+    if let Ty::Function { params, return_: _ } = ty.unalias() {
         for param in params.deref().iter() {
-            if let Some(label) = param.0.clone() {
+            if let Some(label) = param.0.clone().filter(|label| !used.contains(label)) {
                 let replace = format!("{label}: $1");
                 let mut item = CompletionItem {
                     label: label.clone(),
@@ -251,6 +264,7 @@ fn complete_labels(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) -
                     description: None,
                     documentation: None,
                     is_snippet: true,
+                    is_deprecated: false,
                 };
 
                 item.relevance.is_local = true;
@@ -274,6 +288,7 @@ fn complete_at(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) {
         description: None,
         documentation: None,
         is_snippet: true,
+        is_deprecated: false,
     });
     acc.push(CompletionItem {
         label: "target(erlang)".into(),
@@ -285,6 +300,7 @@ fn complete_at(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) {
         description: None,
         documentation: None,
         is_snippet: true,
+        is_deprecated: false,
     });
     acc.push(CompletionItem {
         label: "external(javascript, ..)".into(),
@@ -296,6 +312,7 @@ fn complete_at(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) {
         description: None,
         documentation: None,
         is_snippet: true,
+        is_deprecated: false,
     });
     acc.push(CompletionItem {
         label: "external(erlang, ..)".into(),
@@ -307,6 +324,7 @@ fn complete_at(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) {
         description: None,
         documentation: None,
         is_snippet: true,
+        is_deprecated: false,
     });
 }
 
@@ -318,8 +336,14 @@ fn complete_dot(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) -> Op
                 let analyzer = ctx.sema.analyze(it.base()?.syntax())?;
                 let ty = analyzer.type_of_expr(&it.base()?);
 
-                if let Some(Ty::Adt { adt_id, .. }) = ty {
-                    let fields = Adt {id: adt_id}.common_fields(ctx.db.upcast());
+                if let Some(Ty::Adt { adt_id, .. }) = ty.as_ref().map(Ty::unalias) {
+                    let adt = Adt { id: *adt_id };
+                    // Opaque types keep their fields to their own module.
+                    let fields = if adt.is_opaque_to(ctx.db.upcast(), ctx.module) {
+                        HashMap::new()
+                    } else {
+                        adt.common_fields(ctx.db.upcast())
+                    };
                     for (name, field) in fields {
                         acc.push(CompletionItem {
                             label: name.clone(),
@@ -331,6 +355,7 @@ fn complete_dot(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) -> Op
                             description: None,
                             documentation: None,
                             is_snippet: false,
+                            is_deprecated: false,
                         })
                     }
                 };
@@ -340,6 +365,9 @@ fn complete_dot(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) -> Op
                 let module_items = ctx.db.module_scope(file);
 
                 for (def, _) in module_items.declarations().flatten().filter(|item| item.1 != Visibility::Private) {
+                    if ModuleDef::from(def.clone()).is_internal_to(ctx.db.upcast(), ctx.package) {
+                        continue;
+                    }
                     match def {
                         ModuleDefId::FunctionId(it) => {
                             acc.push(render::render_fn(&ctx, it));
@@ -372,11 +400,7 @@ fn complete_dot(acc: &mut Vec<CompletionItem>, ctx: CompletionContext<'_>) -> Op
 }
 
 fn complete_import(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) -> Option<()> {
-    if let Some(module_path) = ctx
-        .tok
-        .parent_ancestors()
-        .find_map(|t| ast::ModulePath::cast(t))
-    {
+    if let Some(module_path) = ctx.tok.parent_ancestors().find_map(ast::ModulePath::cast) {
         // ToDo: get all visible modules
         for (_, name) in ctx.package.visible_modules(ctx.db.upcast()).iter() {
             acc.push(CompletionItem {
@@ -389,6 +413,7 @@ fn complete_import(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) -
                 description: None,
                 documentation: None,
                 is_snippet: false,
+                is_deprecated: false,
             })
         }
     };
@@ -412,6 +437,10 @@ fn complete_expr(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) ->
     };
 
     for (name, def) in resolver.values_names_in_scope() {
+        let module_def = def.module_def();
+        if module_def.map_or(false, |it| it.is_internal_to(ctx.db.upcast(), ctx.package)) {
+            continue;
+        }
         let kind = match def {
             ResolveResult::Module(_) => CompletionItemKind::Module,
             ResolveResult::ModuleConstant(_) => CompletionItemKind::Function,
@@ -428,8 +457,10 @@ fn complete_expr(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) ->
             }
             ResolveResult::Variant(it) => acc.push(render::render_variant(ctx, &it.into())),
             ResolveResult::Local(it) => {
-                let mut relevance = CompletionRelevance::default();
-                relevance.is_local = true;
+                let relevance = CompletionRelevance {
+                    is_local: true,
+                    ..Default::default()
+                };
 
                 acc.push(CompletionItem {
                     label: name.clone(),
@@ -437,10 +468,11 @@ fn complete_expr(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) ->
                     replace: format!("{}", name).into(),
                     kind,
                     signature: Some(it.ty(ctx.db).display(ctx.db).to_string()),
-                    relevance: relevance,
+                    relevance,
                     description: None,
                     documentation: None,
                     is_snippet: false,
+                    is_deprecated: false,
                 })
             }
             _ => acc.push(CompletionItem {
@@ -453,6 +485,8 @@ fn complete_expr(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) ->
                 description: None,
                 documentation: None,
                 is_snippet: true,
+                is_deprecated: module_def
+                    .map_or(false, |it| it.attrs(ctx.db.upcast()).deprecated.is_some()),
             }),
         };
     }
@@ -460,10 +494,11 @@ fn complete_expr(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext<'_>) ->
     let module_data = ctx.db.module_items(expr_ptr.file_id);
 
     for (_, import) in module_data.module_imports() {
-        let module =
-            resolver.resolve_module(&import.as_name.as_ref().unwrap_or_else(|| &import.accessor));
+        let module = resolver.resolve_module(import.as_name.as_ref().unwrap_or(&import.accessor));
 
-        module.map(|id| acc.push(render::render_module(ctx, &id, import.as_name.clone())));
+        if let Some(id) = module {
+            acc.push(render::render_module(ctx, &id, import.as_name.clone()))
+        }
     }
 
     Some(())
@@ -483,6 +518,7 @@ fn complete_snippet(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext) {
         documentation: None,
         relevance: CompletionRelevance::default(),
         is_snippet: true,
+        is_deprecated: false,
     });
     acc.push(CompletionItem {
         label: "import".into(),
@@ -494,6 +530,7 @@ fn complete_snippet(acc: &mut Vec<CompletionItem>, ctx: &CompletionContext) {
         description: None,
         documentation: None,
         is_snippet: true,
+        is_deprecated: false,
     });
 }
 
@@ -503,7 +540,6 @@ mod tests {
     use crate::tests::TestDB;
     use expect_test::{expect, Expect};
 
-    #[allow(dead_code)]
     #[track_caller]
     fn check_no(fixture: &str, label: &str) {
         let (db, f) = TestDB::from_fixture(fixture).unwrap();
@@ -549,6 +585,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn opaque_type() {
+        let lib = r#"
+#- lib.gleam
+pub opaque type Token { Secret(value: Int) }
+pub type Open { Open(value: Int) }
+#- main.gleam
+import lib
+"#;
+        let labels = |src: &str| {
+            let (db, f) = TestDB::from_fixture(&format!("{lib}{src}")).unwrap();
+            super::completions(&db, f[0], Some('.'))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|item| item.label)
+                .collect::<Vec<_>>()
+        };
+        assert!(labels("fn main(o: lib.Open) { o.$0 }").contains(&"value".into()));
+        assert!(!labels("fn main(t: lib.Token) { t.$0 }").contains(&"value".into()));
+        assert!(!labels("fn main() { lib.$0 }").contains(&"Secret".into()));
+    }
+
+    #[test]
+    fn deprecated_and_internal() {
+        let completions = |fixture: &str| {
+            let (db, f) = TestDB::from_fixture(fixture).unwrap();
+            super::completions(&db, f[0], Some('.')).unwrap_or_default()
+        };
+        let items = completions(
+            r#"
+#- lib.gleam package:dep
+@deprecated("Use `new` instead")
+pub fn old() { 1 }
+@internal
+pub fn hidden() { 1 }
+#- local.gleam
+@internal
+pub fn shared() { 1 }
+#- main.gleam
+import lib
+import local
+fn main() { lib.$0 }
+"#,
+        );
+        let old = items.iter().find(|item| item.label == "old").unwrap();
+        assert!(old.is_deprecated);
+        assert!(!items.iter().any(|item| item.label == "hidden"));
+
+        let items = completions(
+            r#"
+#- local.gleam
+@internal
+pub fn shared() { 1 }
+#- main.gleam
+import local
+fn main() { local.$0 }
+"#,
+        );
+        let shared = items.iter().find(|item| item.label == "shared").unwrap();
+        assert!(!shared.is_deprecated);
+    }
+
     #[test]
     fn labelled_args() {
         check(
@@ -562,4 +660,21 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn record_update_labels() {
+        check(
+            r#"type Pet { Dog(name: String, age: Int) }
+            fn main(pet) { Dog(..pet, a$0) }"#,
+            "age",
+            expect![[r#"
+                (Keyword) type Pet { Dog(name: String, age: Int) }
+                            fn main(pet) { Dog(..pet, age: $1) }"#]],
+        );
+        check_no(
+            r#"type Pet { Dog(name: String, age: Int) }
+            fn main(pet) { Dog(..pet, age: 1, n$0) }"#,
+            "age",
+        );
+    }
 }
@@ -19,15 +19,16 @@ pub fn render_fn(ctx: &CompletionContext<'_>, id: &FunctionId) -> CompletionItem
     let docs = it.docs(ctx.db.upcast());
 
     CompletionItem {
-        label: name.clone().into(),
+        label: name.clone(),
         source_range: ctx.source_range,
-        replace: name.into(),
+        replace: name,
         kind: CompletionItemKind::Function,
         signature: Some(it.ty(ctx.db).display(ctx.db).to_string()),
         relevance: CompletionRelevance::default(),
         description: None,
         documentation: Some(docs),
         is_snippet: false,
+        is_deprecated: it.attrs(ctx.db.upcast()).deprecated.is_some(),
     }
 }
 
@@ -42,15 +43,16 @@ pub fn render_variant(ctx: &CompletionContext<'_>, id: &VariantId) -> Completion
     let docs = it.docs(ctx.db.upcast());
 
     CompletionItem {
-        label: name.clone().into(),
+        label: name.clone(),
         source_range: ctx.source_range,
-        replace: name.into(),
+        replace: name,
         kind: CompletionItemKind::Function,
         signature: Some(Adt { id: it.parent }.name(ctx.db.upcast()).into()),
         relevance: CompletionRelevance::default(),
         description: None,
         documentation: Some(docs),
         is_snippet: false,
+        is_deprecated: it.parent().attrs(ctx.db.upcast()).deprecated.is_some(),
     }
 }
 
@@ -65,14 +67,15 @@ pub fn render_module(
     let docs = it.docs(ctx.db.upcast());
 
     CompletionItem {
-        label: label.clone().into(),
+        label: label.clone(),
         source_range: ctx.source_range,
-        replace: label.into(),
+        replace: label,
         kind: CompletionItemKind::Module,
         relevance: CompletionRelevance::default(),
         signature: Some(format!("import {}", it.name(ctx.db.upcast()).to_string())),
         description: None,
         documentation: Some(docs),
         is_snippet: false,
+        is_deprecated: false,
     }
 }
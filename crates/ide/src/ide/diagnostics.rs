@@ -1,25 +1,599 @@
-use crate::{DefDatabase, Diagnostic, FileId};
+use syntax::ast::{self, AstNode};
+use syntax::{match_ast, SyntaxKind, SyntaxNode, TextRange};
 
-pub(crate) fn diagnostics(db: &dyn DefDatabase, file: FileId) -> Vec<Diagnostic> {
+use crate::def::body::{Body, BodySourceMap};
+use crate::def::hir::{BuiltIn, Module, ModuleDef};
+use crate::def::hir_def::{ConstLoc, FunctionLoc, TypeAliasId, TypeAliasLoc};
+use crate::def::module::{Expr, ExprId, ExprOrPatternId, PatternId, TypeRef};
+use crate::def::resolver::{resolver_for_toplevel, ResolveResult, Resolver};
+use crate::def::resolver_for_expr;
+use crate::def::semantics::{referenced_def, Definition};
+use crate::ty::display::TyDisplay;
+use crate::ty::{
+    check_patterns, CallErrorKind, InferenceResult, PatternDiagnostic, TyDatabase, UnresolvedName,
+};
+use crate::{Diagnostic, DiagnosticKind, FileId, FileRange, Target};
+
+pub(crate) fn diagnostics(db: &dyn TyDatabase, file: FileId) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
 
     // Parsing.
     let parse = db.parse(file);
     diags.extend(parse.errors().iter().map(|&err| Diagnostic::from(err)));
+    let root = parse.syntax_node();
 
     let items = db.module_items(file);
     diags.extend(items.diagnostics.iter().cloned());
 
+    // Name resolution.
+    let visible_modules = Module { id: file }
+        .package(db.upcast())
+        .visible_modules(db.upcast());
+    for (_, import) in items.module_imports() {
+        if visible_modules.file_for_module_name(&import.name).is_some() {
+            continue;
+        }
+        let import_node = import.ast_ptr.to_node(&root);
+        let range = import_node.module_path().map_or_else(
+            || import_node.syntax().text_range(),
+            |p| p.syntax().text_range(),
+        );
+        diags.push(Diagnostic::new(
+            range,
+            DiagnosticKind::UnresolvedModule {
+                name: import.name.to_string(),
+            },
+        ));
+    }
+    unresolved_types(db, file, &root, &mut diags);
+    cyclic_aliases(db, file, &root, &mut diags);
+
+    // Unfinished code.
+    diags.extend(
+        root.descendants()
+            .filter_map(ast::Todo::cast)
+            .map(|todo| Diagnostic::new(todo.syntax().text_range(), DiagnosticKind::Todo)),
+    );
+
+    // Type inference.
+    let target = db.package_graph().target().clone();
+    for (func_idx, _) in items.functions() {
+        let fn_id = db.intern_function(FunctionLoc {
+            file_id: file,
+            value: func_idx,
+        });
+        let infer = db.infer_function(fn_id);
+        let (body, source_map) = db.body_with_source_map(fn_id);
+        let resolver = |expr| resolver_for_expr(db.upcast(), fn_id, expr);
+        inference_diagnostics(db, &root, &infer, &source_map, &mut diags);
+        reference_diagnostics(
+            db,
+            &root,
+            &target,
+            (&body, &source_map),
+            &infer,
+            &resolver,
+            &mut diags,
+        );
+        call_diagnostics(
+            db,
+            &root,
+            (&body, &source_map),
+            &infer,
+            &resolver,
+            &mut diags,
+        );
+
+        // Pattern checking.
+        for diag in check_patterns(db, fn_id) {
+            let diag = match diag {
+                PatternDiagnostic::InexhaustiveCase { case, missing } => {
+                    let Some(case) = case_node(&source_map, &root, case) else {
+                        continue;
+                    };
+                    // Only the head of the case, the clauses are usually many lines.
+                    let end = case
+                        .subjects()
+                        .last()
+                        .map_or(case.syntax().text_range().end(), |s| {
+                            s.syntax().text_range().end()
+                        });
+                    let range = TextRange::new(case.syntax().text_range().start(), end);
+                    Diagnostic::new(range, DiagnosticKind::InexhaustiveCase { missing })
+                }
+                PatternDiagnostic::InexhaustiveLet { pattern, missing } => {
+                    let Some(range) = source_range(&source_map, pattern.into()) else {
+                        continue;
+                    };
+                    Diagnostic::new(range, DiagnosticKind::InexhaustiveLet { missing })
+                }
+                PatternDiagnostic::UnreachableClause { case, clause } => {
+                    let Some(clause) = case_node(&source_map, &root, case)
+                        .and_then(|case| case.clauses().nth(clause))
+                    else {
+                        continue;
+                    };
+                    let mut patterns = clause.patterns().map(|p| p.syntax().text_range());
+                    let Some(first) = patterns.next() else {
+                        continue;
+                    };
+                    let range = patterns.fold(first, |acc, range| acc.cover(range));
+                    Diagnostic::new(range, DiagnosticKind::UnreachableClause)
+                }
+            };
+            diags.push(diag);
+        }
+    }
+
+    for (const_idx, _) in items.constants() {
+        let const_id = db.intern_const(ConstLoc {
+            file_id: file,
+            value: const_idx,
+        });
+        let infer = db.infer_const(const_id);
+        let (body, source_map) = db.const_body_with_source_map(const_id);
+        let resolver = |_| resolver_for_toplevel(db.upcast(), file);
+        inference_diagnostics(db, &root, &infer, &source_map, &mut diags);
+        reference_diagnostics(
+            db,
+            &root,
+            &target,
+            (&body, &source_map),
+            &infer,
+            &resolver,
+            &mut diags,
+        );
+        call_diagnostics(
+            db,
+            &root,
+            (&body, &source_map),
+            &infer,
+            &resolver,
+            &mut diags,
+        );
+    }
+
     diags
 }
 
+fn inference_diagnostics(
+    db: &dyn TyDatabase,
+    root: &SyntaxNode,
+    infer: &InferenceResult,
+    source_map: &BodySourceMap,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for (source, mismatch) in infer.type_mismatches() {
+        let Some(range) = source_range(source_map, *source) else {
+            continue;
+        };
+        diags.push(Diagnostic::new(
+            range,
+            DiagnosticKind::TypeMismatch {
+                expected: mismatch.expected.display(db).to_string(),
+                actual: mismatch.actual.display(db).to_string(),
+            },
+        ));
+    }
+    for (pattern, mismatch) in infer.annotation_mismatches() {
+        let Some(range) = annotation_range(source_map, root, *pattern) else {
+            continue;
+        };
+        diags.push(Diagnostic::new(
+            range,
+            DiagnosticKind::TypeMismatch {
+                expected: mismatch.expected.display(db).to_string(),
+                actual: mismatch.actual.display(db).to_string(),
+            },
+        ));
+    }
+    for (source, name) in infer.unresolved_names() {
+        let Some(range) = unresolved_name_range(source_map, root, *source, name) else {
+            continue;
+        };
+        let kind = match name {
+            UnresolvedName::Variable(name) => DiagnosticKind::UnresolvedVariable {
+                name: name.to_string(),
+            },
+            UnresolvedName::Constructor(name) => DiagnosticKind::UnresolvedConstructor {
+                name: name.to_string(),
+            },
+            UnresolvedName::Module(name) => DiagnosticKind::UnresolvedModule {
+                name: name.to_string(),
+            },
+            UnresolvedName::ModuleMember { module, name } => {
+                DiagnosticKind::UnresolvedModuleMember {
+                    module: module.to_string(),
+                    name: name.to_string(),
+                }
+            }
+            UnresolvedName::Field { constructor, label } => DiagnosticKind::UnresolvedField {
+                constructor: constructor.to_string(),
+                label: label.to_string(),
+            },
+        };
+        diags.push(Diagnostic::new(range, kind));
+    }
+    for (source, adt) in infer.opaque_uses() {
+        let Some(range) = opaque_use_range(source_map, root, *source) else {
+            continue;
+        };
+        diags.push(Diagnostic::new(
+            range,
+            DiagnosticKind::OpaqueTypeAccess {
+                name: adt.name(db.upcast()).to_string(),
+            },
+        ));
+    }
+}
+
+/// Checks on the module level definitions a body refers to: deprecated items and external
+/// functions without an implementation for the workspace's target.
+fn reference_diagnostics(
+    db: &dyn TyDatabase,
+    root: &SyntaxNode,
+    target: &Target,
+    (body, source_map): (&Body, &BodySourceMap),
+    infer: &InferenceResult,
+    resolver: &dyn Fn(ExprId) -> Resolver,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for (expr, _) in body.exprs() {
+        let Some(def) = referenced_def(body, infer, resolver, expr) else {
+            continue;
+        };
+        let Some(node) = source_map.node_for_expr(expr) else {
+            continue;
+        };
+        let range = match node.value.to_node(root) {
+            ast::Expr::FieldAccessExpr(it) => it.label().map(|l| l.syntax().text_range()),
+            _ => None,
+        }
+        .unwrap_or_else(|| node.value.text_range());
+        let name = def.name(db.upcast()).to_string();
+
+        if let Some(message) = def.attrs(db.upcast()).deprecated {
+            diags.push(Diagnostic::new(
+                range,
+                DiagnosticKind::Deprecated {
+                    name: name.clone(),
+                    message: message.to_string(),
+                },
+            ));
+        }
+        if let ModuleDef::Function(func) = def {
+            // Body-less functions without any externals are still being written.
+            if !func.externals(db.upcast()).is_empty() && !func.supports_target(db.upcast(), target)
+            {
+                diags.push(Diagnostic::new(
+                    range,
+                    DiagnosticKind::MissingTargetImplementation {
+                        name,
+                        target: target.to_string(),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Arguments that don't fit the parameters of the called function, with a note pointing at the
+/// function when it is a module level definition.
+fn call_diagnostics(
+    db: &dyn TyDatabase,
+    root: &SyntaxNode,
+    (body, source_map): (&Body, &BodySourceMap),
+    infer: &InferenceResult,
+    resolver: &dyn Fn(ExprId) -> Resolver,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for (source, error) in infer.call_errors() {
+        let Some(range) = call_error_range(source_map, root, *source, &error.kind) else {
+            continue;
+        };
+        let kind = match &error.kind {
+            CallErrorKind::WrongArity { expected, found } => DiagnosticKind::WrongArgumentCount {
+                expected: *expected,
+                found: *found,
+            },
+            CallErrorKind::UnknownLabel(label) => DiagnosticKind::UnknownLabel {
+                label: label.to_string(),
+            },
+            CallErrorKind::DuplicateLabel(label) => DiagnosticKind::DuplicateLabel {
+                label: label.to_string(),
+            },
+            CallErrorKind::PositionalAfterLabelled => DiagnosticKind::PositionalAfterLabelled,
+            CallErrorKind::UnlabelledUpdateField => DiagnosticKind::UnlabelledUpdateField,
+        };
+        let mut diag = Diagnostic::new(range, kind);
+
+        // `use f <- ...` calls `f` without an argument list.
+        let callee = match &body[error.call] {
+            Expr::Call { func, .. } => *func,
+            Expr::RecordUpdate { constructor, .. } => *constructor,
+            _ => error.call,
+        };
+        if let Some(def) = referenced_def(body, infer, resolver, callee) {
+            let name = def.name(db.upcast());
+            if let Some(nav) = Definition::from(def).to_nav(db) {
+                let frange = FileRange {
+                    file_id: nav.file_id,
+                    range: nav.focus_range,
+                };
+                diag = diag.with_note(frange, format!("`{name}` is defined here"));
+            }
+        }
+        diags.push(diag);
+    }
+}
+
+fn case_node(source_map: &BodySourceMap, root: &SyntaxNode, case: ExprId) -> Option<ast::Case> {
+    match source_map.node_for_expr(case)?.value.to_node(root) {
+        ast::Expr::Case(case) => Some(case),
+        _ => None,
+    }
+}
+
+fn source_range(source_map: &BodySourceMap, source: ExprOrPatternId) -> Option<TextRange> {
+    match source {
+        ExprOrPatternId::ExprId(expr) => source_map
+            .node_for_expr(expr)
+            .map(|src| src.value.text_range()),
+        ExprOrPatternId::PatternId(pat) => source_map
+            .node_for_pattern(pat)
+            .map(|src| src.value.text_range()),
+    }
+}
+
+/// Narrows qualified names down to the part that failed to resolve.
+fn unresolved_name_range(
+    source_map: &BodySourceMap,
+    root: &SyntaxNode,
+    source: ExprOrPatternId,
+    name: &UnresolvedName,
+) -> Option<TextRange> {
+    let narrowed = match (source, name) {
+        (ExprOrPatternId::ExprId(expr), UnresolvedName::ModuleMember { .. }) => {
+            match source_map.node_for_expr(expr)?.value.to_node(root) {
+                ast::Expr::FieldAccessExpr(it) => it.label().map(|l| l.syntax().text_range()),
+                _ => None,
+            }
+        }
+        (ExprOrPatternId::ExprId(expr), UnresolvedName::Field { .. }) => {
+            let value = source_map.node_for_expr(expr)?.value.to_node(root);
+            ast::Arg::cast(value.syntax().parent()?)?
+                .label()
+                .map(|l| l.syntax().text_range())
+        }
+        (ExprOrPatternId::PatternId(pat), _) => {
+            match source_map.node_for_pattern(pat)?.value.to_node(root) {
+                ast::Pattern::VariantRef(it) => match name {
+                    UnresolvedName::Module(_) => it.module().map(|m| m.syntax().text_range()),
+                    _ => it.variant().map(|v| v.syntax().text_range()),
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    narrowed.or_else(|| source_range(source_map, source))
+}
+
+/// Narrows label errors down to the label and wrong arities down to the argument list.
+fn call_error_range(
+    source_map: &BodySourceMap,
+    root: &SyntaxNode,
+    source: ExprId,
+    kind: &CallErrorKind,
+) -> Option<TextRange> {
+    let value = source_map.node_for_expr(source)?.value;
+    let narrowed = match kind {
+        CallErrorKind::UnknownLabel(_) | CallErrorKind::DuplicateLabel(_) => {
+            let node = value.to_node(root);
+            ast::Arg::cast(node.syntax().parent()?)?
+                .label()
+                .map(|l| l.syntax().text_range())
+        }
+        CallErrorKind::WrongArity { .. } => match value.to_node(root) {
+            ast::Expr::ExprCall(it) => it.arguments().map(|a| a.syntax().text_range()),
+            _ => None,
+        },
+        CallErrorKind::PositionalAfterLabelled | CallErrorKind::UnlabelledUpdateField => None,
+    };
+    Some(narrowed.unwrap_or_else(|| value.text_range()))
+}
+
+/// The annotation of the `let` or `use` binding `pattern`.
+fn annotation_range(
+    source_map: &BodySourceMap,
+    root: &SyntaxNode,
+    pattern: PatternId,
+) -> Option<TextRange> {
+    let value = source_map.node_for_pattern(pattern)?.value;
+    let parent = value.to_node(root).syntax().parent()?;
+    let annotation = match_ast! {
+        match parent {
+            ast::StmtLet(it) => it.annotation(),
+            ast::UseAssignment(it) => it.annotation(),
+            _ => None,
+        }
+    };
+    Some(annotation.map_or_else(|| value.text_range(), |it| it.syntax().text_range()))
+}
+
+/// Narrows uses of opaque internals down to the constructor or field name.
+fn opaque_use_range(
+    source_map: &BodySourceMap,
+    root: &SyntaxNode,
+    source: ExprOrPatternId,
+) -> Option<TextRange> {
+    let narrowed = match source {
+        ExprOrPatternId::ExprId(expr) => {
+            match source_map.node_for_expr(expr)?.value.to_node(root) {
+                ast::Expr::FieldAccessExpr(it) => it.label().map(|l| l.syntax().text_range()),
+                _ => None,
+            }
+        }
+        ExprOrPatternId::PatternId(pat) => {
+            match source_map.node_for_pattern(pat)?.value.to_node(root) {
+                ast::Pattern::VariantRef(it) => it.variant().map(|v| v.syntax().text_range()),
+                _ => None,
+            }
+        }
+    };
+    narrowed.or_else(|| source_range(source_map, source))
+}
+
+fn cyclic_aliases(
+    db: &dyn TyDatabase,
+    file: FileId,
+    root: &SyntaxNode,
+    diags: &mut Vec<Diagnostic>,
+) {
+    for (idx, alias) in db.module_items(file).type_alias() {
+        let alias_id = db.intern_type_alias(TypeAliasLoc {
+            file_id: file,
+            value: idx,
+        });
+        if !alias_reaches(db, alias_id, alias_id, &mut Vec::new()) {
+            continue;
+        }
+        let node = alias.ast_ptr.to_node(root);
+        let range = node.name().map_or_else(
+            || node.syntax().text_range(),
+            |name| name.syntax().text_range(),
+        );
+        diags.push(Diagnostic::new(
+            range,
+            DiagnosticKind::CyclicTypeAlias {
+                name: alias.name.to_string(),
+            },
+        ));
+    }
+}
+
+/// Whether expanding `from` ends up at `target` again, possibly through other aliases.
+fn alias_reaches(
+    db: &dyn TyDatabase,
+    from: TypeAliasId,
+    target: TypeAliasId,
+    visited: &mut Vec<TypeAliasId>,
+) -> bool {
+    let loc = db.lookup_intern_type_alias(from);
+    let items = db.module_items(loc.file_id);
+    let resolver = resolver_for_toplevel(db.upcast(), loc.file_id);
+    let mut stack: Vec<&TypeRef> = items[loc.value].body.iter().collect();
+    while let Some(ty) = stack.pop() {
+        match ty {
+            TypeRef::Adt {
+                module,
+                name,
+                params,
+            } => {
+                stack.extend(params.iter());
+                let resolved = match module {
+                    Some(module) => resolver.resolve_module(module).and_then(|file| {
+                        resolver_for_toplevel(db.upcast(), file).resolve_type(name)
+                    }),
+                    None => resolver.resolve_type(name),
+                };
+                let Some(ResolveResult::TypeAlias(alias)) = resolved else {
+                    continue;
+                };
+                if alias.id == target {
+                    return true;
+                }
+                if !visited.contains(&alias.id) {
+                    visited.push(alias.id);
+                    if alias_reaches(db, alias.id, target, visited) {
+                        return true;
+                    }
+                }
+            }
+            TypeRef::Function { params, return_ } => {
+                stack.extend(params.iter());
+                stack.push(return_);
+            }
+            TypeRef::Tuple { fields } => stack.extend(fields.iter()),
+            TypeRef::Hole | TypeRef::Unknown | TypeRef::Generic { .. } => {}
+        }
+    }
+    false
+}
+
+fn unresolved_types(
+    db: &dyn TyDatabase,
+    file: FileId,
+    root: &SyntaxNode,
+    diags: &mut Vec<Diagnostic>,
+) {
+    let resolver = resolver_for_toplevel(db.upcast(), file);
+    for type_ref in root.descendants().filter_map(ast::TypeNameRef::cast) {
+        let Some(constructor) = type_ref.constructor_name() else {
+            continue;
+        };
+        let (Some(token), Some(name)) = (constructor.token(), constructor.text()) else {
+            continue;
+        };
+        // Lowercase names are generic parameters.
+        if token.kind() != SyntaxKind::U_IDENT {
+            continue;
+        }
+        let kind = match type_ref.module().and_then(|m| Some((m.text()?, m))) {
+            Some((module, module_node)) => match resolver.resolve_module(&module) {
+                Some(module_file) => {
+                    let module_resolver = resolver_for_toplevel(db.upcast(), module_file);
+                    if module_resolver.resolve_type(&name).is_some() {
+                        continue;
+                    }
+                    DiagnosticKind::UnresolvedModuleMember {
+                        module: module.to_string(),
+                        name: name.to_string(),
+                    }
+                }
+                None => {
+                    diags.push(Diagnostic::new(
+                        module_node.syntax().text_range(),
+                        DiagnosticKind::UnresolvedModule {
+                            name: module.to_string(),
+                        },
+                    ));
+                    continue;
+                }
+            },
+            None => {
+                if resolver.resolve_type(&name).is_some() || BuiltIn::TYPES.contains(&name.as_str())
+                {
+                    continue;
+                }
+                DiagnosticKind::UnresolvedType {
+                    name: name.to_string(),
+                }
+            }
+        };
+        diags.push(Diagnostic::new(constructor.syntax().text_range(), kind));
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use crate::tests::TestDB;
+    use crate::{SourceDatabase, Target};
     use expect_test::{expect, Expect};
 
     fn check(fixture: &str, expect: Expect) {
-        let (db, file_id) = TestDB::single_file(fixture).unwrap();
+        check_target(fixture, Target::default(), expect);
+    }
+
+    fn check_target(fixture: &str, target: Target, expect: Expect) {
+        let (mut db, f) = TestDB::from_fixture(fixture).unwrap();
+        let mut graph = (*db.package_graph()).clone();
+        graph.set_target(target);
+        db.set_package_graph(Arc::new(graph));
+        // Multi-file fixtures mark the file to check with `$0`.
+        let file_id = f.markers().first().map_or(f.files()[0], |m| m.file_id);
         let diags = super::diagnostics(&db, file_id);
         assert!(!diags.is_empty());
         let mut got = diags
@@ -45,6 +619,515 @@ mod tests {
         );
     }
 
+    #[test]
+    fn type_mismatch_binary_op() {
+        check(
+            r#"fn main() { 1 + "a" }"#,
+            expect![[r#"16..19: TypeMismatch { expected: "Int", actual: "String" }"#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_bool_ops() {
+        check(
+            r#"fn main() { 1 && !"a" || -.1 }"#,
+            expect![[r#"
+                18..21: TypeMismatch { expected: "Bool", actual: "String" }
+                12..13: TypeMismatch { expected: "Bool", actual: "Int" }
+                27..28: TypeMismatch { expected: "Float", actual: "Int" }
+                25..28: TypeMismatch { expected: "Bool", actual: "Float" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_negate_float() {
+        check(
+            r#"fn main(a: Float) { #(-a, -1.5) }"#,
+            expect![[r#"23..24: TypeMismatch { expected: "Int", actual: "Float" }"#]],
+        );
+    }
+
+    #[test]
+    fn todo_and_panic() {
+        check(
+            r#"fn main(a) { case a { 1 -> todo 2 -> panic as 3 _ -> todo as "soon" } }"#,
+            expect![[r#"
+                27..31: Todo
+                53..67: Todo
+                46..47: TypeMismatch { expected: "String", actual: "Int" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_list_and_case() {
+        check(
+            r#"
+fn main(a) {
+    let b = [1, 2.0]
+    case a {
+        1 -> "a"
+        "b" -> 2
+    }
+}"#,
+            expect![[r#"
+                29..32: TypeMismatch { expected: "Int", actual: "Float" }
+                72..75: TypeMismatch { expected: "Int", actual: "String" }
+                79..80: TypeMismatch { expected: "String", actual: "Int" }
+                38..44: InexhaustiveCase { missing: ["_"] }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unresolved_names() {
+        check(
+            "fn main() { let a = b Foo(a) case a { Bar -> 1 } }",
+            expect![[r#"
+                20..21: UnresolvedVariable { name: "b" }
+                22..25: UnresolvedConstructor { name: "Foo" }
+                38..41: UnresolvedConstructor { name: "Bar" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unresolved_types() {
+        check(
+            "type A(a) { A(x: Missing, y: a) } fn f(b: Nope) -> List(Result(Int, Gone)) { todo }",
+            expect![[r#"
+                17..24: UnresolvedType { name: "Missing" }
+                42..46: UnresolvedType { name: "Nope" }
+                68..72: UnresolvedType { name: "Gone" }
+                77..81: Todo
+            "#]],
+        );
+    }
+
+    #[test]
+    fn unresolved_qualified() {
+        check(
+            r#"
+#- lib.gleam
+pub type Kind { Kind }
+pub fn f() { 1 }
+
+#- main.gleam
+import lib
+import missing/thing
+
+fn main(x: lib.Nope, y: other.Kind) {
+    lib.f()
+    lib.g()$0
+    case x { lib.Bar -> 1 other.Kind -> 2 }
+}
+"#,
+            expect![[r#"
+                18..31: UnresolvedModule { name: "missing/thing" }
+                48..52: UnresolvedModuleMember { module: "lib", name: "Nope" }
+                57..62: UnresolvedModule { name: "other" }
+                91..92: UnresolvedModuleMember { module: "lib", name: "g" }
+                112..115: UnresolvedModuleMember { module: "lib", name: "Bar" }
+                121..126: UnresolvedModule { name: "other" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn record_update_fields() {
+        check(
+            r#"
+type Pet { Dog(name: String, age: Int) }
+fn main(pet) { Dog(..pet, age: "old", colour: 1) }"#,
+            expect![[r#"
+                72..77: TypeMismatch { expected: "Int", actual: "String" }
+                79..85: UnresolvedField { constructor: "Dog", label: "colour" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn record_update_unlabelled_field() {
+        check(
+            r#"
+type Pet { Dog(name: String, age: Int) }
+fn main(pet) { Dog(..pet, 1) }"#,
+            expect![[r#"
+                67..68: UnlabelledUpdateField
+                    11..38: `Dog` is defined here
+            "#]],
+        );
+    }
+
+    #[test]
+    fn opaque_type_access() {
+        check(
+            r#"
+#- lib.gleam
+pub opaque type Token { Token(secret: Int) }
+pub fn new() { Token(1) }
+fn peek(t: Token) { t.secret }
+#- main.gleam
+import lib
+fn main(t: lib.Token) {
+    let lib.Token(s) = lib.new()$0
+    lib.Token(s).secret + t.secret
+}
+"#,
+            expect![[r#"
+                47..52: OpaqueTypeAccess { name: "Token" }
+                76..81: OpaqueTypeAccess { name: "Token" }
+                85..91: OpaqueTypeAccess { name: "Token" }
+                96..102: OpaqueTypeAccess { name: "Token" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn missing_target_implementation() {
+        let fixture = r#"
+#- ffi.gleam
+@external(erlang, "ffi", "beam")
+pub fn beam() -> Int
+@external(javascript, "./ffi.mjs", "node")
+pub fn node() -> Int
+@external(erlang, "ffi", "both")
+@external(javascript, "./ffi.mjs", "both")
+pub fn both() -> Int
+@external(erlang, "ffi", "fallback")
+pub fn fallback() -> Int { 1 }
+pub fn unfinished() -> Int
+#- main.gleam
+import ffi
+@external(javascript, "./main.mjs", "local")
+fn local() -> Int
+fn main() {
+    ffi.beam() + ffi.node() + ffi.both() + ffi.fallback() + ffi.unfinished() + local()$0
+}
+"#;
+        check_target(
+            fixture,
+            Target::Erlang,
+            expect![[r#"
+                107..111: MissingTargetImplementation { name: "node", target: "Erlang" }
+                165..170: MissingTargetImplementation { name: "local", target: "Erlang" }
+            "#]],
+        );
+        check_target(
+            fixture,
+            Target::Javascript,
+            expect![[
+                r#"94..98: MissingTargetImplementation { name: "beam", target: "JavaScript" }"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn deprecated() {
+        check(
+            r#"
+#- lib.gleam
+@deprecated("Use `new` instead")
+pub fn old() { 1 }
+@deprecated("Counting is over")
+pub const limit = 10
+@deprecated
+pub type Shape { Circle }
+#- main.gleam
+import lib.{Circle}
+fn main() {
+    #(lib.old(), lib.limit, Circle)$0
+}
+"#,
+            expect![[r#"
+                42..45: Deprecated { name: "old", message: "Use `new` instead" }
+                53..58: Deprecated { name: "limit", message: "Counting is over" }
+                60..66: Deprecated { name: "Circle", message: "" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn call_arguments() {
+        check(
+            r#"
+fn sub(from a: Int, take b: Int) -> Int { a - b }
+fn main() {
+    sub(1)
+    sub(1, 2, 3)
+    sub(take: 1, from: 2)
+    sub(take: 1, 2)
+    sub(1, by: 2)
+    sub(take: 1, take: 2)
+}"#,
+            expect![[r#"
+                69..72: WrongArgumentCount { expected: 2, found: 1 }
+                    3..6: `sub` is defined here
+                80..89: WrongArgumentCount { expected: 2, found: 3 }
+                    3..6: `sub` is defined here
+                133..134: PositionalAfterLabelled
+                    3..6: `sub` is defined here
+                147..149: UnknownLabel { label: "by" }
+                    3..6: `sub` is defined here
+                171..175: DuplicateLabel { label: "take" }
+                    3..6: `sub` is defined here
+            "#]],
+        );
+    }
+
+    #[test]
+    fn annotation_mismatch() {
+        check(
+            r#"
+fn with(f: fn(Int) -> a) -> a { f(1) }
+fn main() {
+    let a: String = 1
+    let b: List(Int) = [a]
+    use c: Float <- with
+    c
+}"#,
+            expect![[r#"
+                62..68: TypeMismatch { expected: "String", actual: "Int" }
+                84..93: TypeMismatch { expected: "List(Int)", actual: "List(String)" }
+                111..116: TypeMismatch { expected: "Float", actual: "Int" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn return_type_mismatch() {
+        check(
+            r#"
+fn main(a: Int) -> String {
+    let b = a
+    b
+}
+fn lambda() {
+    fn(x: Int) -> Float { x }
+}"#,
+            expect![[r#"
+                46..47: TypeMismatch { expected: "String", actual: "Int" }
+                90..91: TypeMismatch { expected: "Float", actual: "Int" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn pipe_arguments() {
+        check(
+            r#"
+fn add(a: Int, b: Int) -> Int { a + b }
+fn main() {
+    "a" |> add(1)
+    1 |> add(2, 3)
+}"#,
+            expect![[r#"
+                56..59: TypeMismatch { expected: "Int", actual: "String" }
+                79..88: TypeMismatch { expected: "fn(Int) -> a", actual: "Int" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn use_call_arguments() {
+        check(
+            r#"
+#- lib.gleam
+pub fn with(value: a, f: fn(a) -> b) -> b { f(value) }
+#- main.gleam
+import lib
+fn main() {
+    use x <- lib.with()
+    use y <- lib.with(1, 2)
+    x + y$0
+}"#,
+            expect![[r#"
+                44..46: WrongArgumentCount { expected: 2, found: 1 }
+                    7..11: `with` is defined here
+                68..74: WrongArgumentCount { expected: 2, found: 3 }
+                    7..11: `with` is defined here
+            "#]],
+        );
+    }
+
+    #[test]
+    fn cyclic_type_alias() {
+        check(
+            r#"
+type A = List(B)
+type B = #(Int, A)
+type C = Int
+type D = fn(C) -> D
+fn main(x: A) { x }"#,
+            expect![[r#"
+                5..6: CyclicTypeAlias { name: "A" }
+                22..23: CyclicTypeAlias { name: "B" }
+                54..55: CyclicTypeAlias { name: "D" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_alias() {
+        check(
+            r#"
+type Handler(a) = fn(String) -> Result(a, Nil)
+fn run(h: Handler(Int)) { h("request") }
+fn main() { run(fn(_) { Ok("one") }) }"#,
+            expect![[
+                r#"104..123: TypeMismatch { expected: "fn(String) -> Result(Int, Nil)", actual: "fn(String) -> Result(String, a)" }"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn module_constants() {
+        check(
+            r#"
+const a: Int = "one"
+const b = missing
+const c = d
+const d = c
+const e = f
+fn f() { e }"#,
+            expect![[r#"
+                15..20: TypeMismatch { expected: "Int", actual: "String" }
+                31..38: UnresolvedVariable { name: "missing" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn constant_cycle_keeps_function_diagnostics() {
+        check(
+            r#"
+const e = f
+fn f() { e + "a" }"#,
+            expect![[r#"25..28: TypeMismatch { expected: "Int", actual: "String" }"#]],
+        );
+    }
+
+    #[test]
+    fn pattern_checking() {
+        check(
+            r#"
+type Pet { Cat Dog }
+fn main(pet, r) {
+    let Ok(x) = r
+    case pet {
+        Cat -> x
+        Cat | Cat -> x
+    }
+}
+"#,
+            expect![[r#"
+                97..106: UnreachableClause
+                61..69: InexhaustiveCase { missing: ["Dog"] }
+                47..52: InexhaustiveLet { missing: ["Error(_)"] }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_case_guard() {
+        check(
+            r#"
+fn main(a) {
+    case a {
+        1 if a -> 1
+        b if b + 1 -> 2
+        _ -> 3
+    }
+}"#,
+            expect![[r#"
+                39..40: TypeMismatch { expected: "Bool", actual: "Int" }
+                59..64: TypeMismatch { expected: "Bool", actual: "Int" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_bit_array_segments() {
+        check(
+            r#"fn main(x) { <<1.0, "a":int, x:size(1.5)>> }"#,
+            expect![[r#"
+                15..18: TypeMismatch { expected: "Int", actual: "Float" }
+                20..23: TypeMismatch { expected: "Int", actual: "String" }
+                36..39: TypeMismatch { expected: "Int", actual: "Float" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_call_args() {
+        check(
+            r#"
+fn add(a: Int, b: String) { a }
+
+fn main() {
+    add("a", 1)
+}"#,
+            expect![[r#"
+                53..56: TypeMismatch { expected: "Int", actual: "String" }
+                58..59: TypeMismatch { expected: "String", actual: "Int" }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_labelled_call_args() {
+        check(
+            r#"
+fn add(a: Int, label b: String) { a }
+
+fn main() {
+    add(label: 1, "a")
+}"#,
+            expect![[r#"
+                66..67: TypeMismatch { expected: "String", actual: "Int" }
+                69..72: TypeMismatch { expected: "Int", actual: "String" }
+                69..72: PositionalAfterLabelled
+                    3..6: `add` is defined here
+            "#]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_arity() {
+        check(
+            r#"
+fn apply(f: fn(Int) -> Int) { f(1) }
+
+fn main() {
+    let pair: #(Int) = #(1, 2)
+    apply(fn(a, b) { a })
+}"#,
+            expect![[r##"
+                91..105: TypeMismatch { expected: "fn(Int) -> Int", actual: "fn(a, b) -> a" }
+                64..70: TypeMismatch { expected: "#(Int)", actual: "#(Int, Int)" }
+            "##]],
+        );
+    }
+
+    #[test]
+    fn type_mismatch_spread_and_use_callback() {
+        check(
+            r#"
+fn each(f: fn(Int) -> Nil) { f(1) }
+
+fn main(x: String) {
+    let xs = [1, ..x]
+    use a <- each
+    a
+}"#,
+            expect![[r#"
+                77..78: TypeMismatch { expected: "List(Int)", actual: "String" }
+                102..103: TypeMismatch { expected: "Nil", actual: "Int" }
+            "#]],
+        );
+    }
+
     // #[test]
     // fn duplicated_param() {
     //     check("fn bla(a, a) {}", expect![[r#"
@@ -0,0 +1,189 @@
+use syntax::{NodeOrToken, SyntaxKind, SyntaxNode, TextRange};
+
+use crate::{DefDatabase, FileId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldKind {
+    Block,
+    Case,
+    Clause,
+    List,
+    Tuple,
+    Adt,
+    Imports,
+    Comment,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fold {
+    pub range: TextRange,
+    pub kind: FoldKind,
+}
+
+/// Everything that spans multiple lines and can be collapsed, in source order.
+pub(crate) fn folding_ranges(db: &dyn DefDatabase, file_id: FileId) -> Vec<Fold> {
+    let root = db.parse(file_id).syntax_node();
+    let mut folds = Vec::new();
+
+    for node in root.descendants() {
+        let (kind, range) = match node.kind() {
+            SyntaxKind::BLOCK => (FoldKind::Block, node.text_range()),
+            SyntaxKind::CASE => (FoldKind::Case, node.text_range()),
+            SyntaxKind::CLAUSE => (FoldKind::Clause, node.text_range()),
+            SyntaxKind::LIST | SyntaxKind::CONSTANT_LIST => (FoldKind::List, node.text_range()),
+            SyntaxKind::TUPLE | SyntaxKind::CONSTANT_TUPLE => (FoldKind::Tuple, node.text_range()),
+            // Keep the documentation and name of the type visible.
+            SyntaxKind::ADT => match adt_body(&node) {
+                Some(range) => (FoldKind::Adt, range),
+                None => continue,
+            },
+            _ => continue,
+        };
+        push_multiline(&root, &mut folds, kind, range);
+    }
+
+    // Runs of imports, only separated by whitespace.
+    let mut imports: Option<TextRange> = None;
+    for child in root.children_with_tokens() {
+        match child.kind() {
+            SyntaxKind::IMPORT => {
+                let range = child.text_range();
+                imports = Some(imports.map_or(range, |run| run.cover(range)));
+            }
+            SyntaxKind::WHITESPACE => {}
+            _ => {
+                if let Some(run) = imports.take() {
+                    push_multiline(&root, &mut folds, FoldKind::Imports, run);
+                }
+            }
+        }
+    }
+    if let Some(run) = imports {
+        push_multiline(&root, &mut folds, FoldKind::Imports, run);
+    }
+
+    // Runs of comments of the same kind on consecutive lines.
+    let mut comments: Option<(SyntaxKind, TextRange)> = None;
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(NodeOrToken::into_token)
+    {
+        let kind = token.kind();
+        match kind {
+            SyntaxKind::COMMENT | SyntaxKind::COMMENT_STATEMENT | SyntaxKind::COMMENT_MODULE => {
+                let range = token.text_range();
+                comments = match comments {
+                    Some((run_kind, run)) if run_kind == kind => Some((kind, run.cover(range))),
+                    run => {
+                        if let Some((_, run)) = run {
+                            push_multiline(&root, &mut folds, FoldKind::Comment, run);
+                        }
+                        Some((kind, range))
+                    }
+                };
+            }
+            // A blank line ends the run.
+            SyntaxKind::WHITESPACE if token.text().matches('\n').count() <= 1 => {}
+            _ => {
+                if let Some((_, run)) = comments.take() {
+                    push_multiline(&root, &mut folds, FoldKind::Comment, run);
+                }
+            }
+        }
+    }
+    if let Some((_, run)) = comments {
+        push_multiline(&root, &mut folds, FoldKind::Comment, run);
+    }
+
+    folds.sort_by_key(|fold| (fold.range.start(), std::cmp::Reverse(fold.range.end())));
+    folds
+}
+
+/// From the opening to the closing brace of a custom type.
+fn adt_body(node: &SyntaxNode) -> Option<TextRange> {
+    let mut tokens = node
+        .children_with_tokens()
+        .filter_map(NodeOrToken::into_token);
+    let open = tokens.find(|t| t.kind() == SyntaxKind::L_BRACE)?;
+    let close = tokens.find(|t| t.kind() == SyntaxKind::R_BRACE)?;
+    Some(open.text_range().cover(close.text_range()))
+}
+
+fn push_multiline(root: &SyntaxNode, folds: &mut Vec<Fold>, kind: FoldKind, range: TextRange) {
+    if root.text().slice(range).contains_char('\n') {
+        folds.push(Fold { range, kind });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::base::SourceDatabase;
+    use crate::tests::TestDB;
+
+    #[track_caller]
+    fn check(fixture: &str, expect: Expect) {
+        let (db, file) = TestDB::single_file(fixture).unwrap();
+        let src = db.file_content(file);
+        let mut got = String::new();
+        for fold in super::folding_ranges(&db, file) {
+            let text = &src[fold.range];
+            let first = text.lines().next().unwrap_or_default();
+            let last = text.lines().last().unwrap_or_default();
+            got += &format!("{:?} {:?}: {first} .. {last}\n", fold.kind, fold.range);
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[test]
+    fn folds() {
+        check(
+            r#"
+//// A module
+//// about shapes.
+
+import gleam/int
+import gleam/list
+
+import gleam/string
+
+/// A shape.
+/// With two lines.
+pub type Shape {
+  Circle(Float)
+  Square(Float)
+}
+
+// Plain comment
+// on two lines
+
+fn main(x) {
+  let xs = [
+    1,
+    2,
+  ]
+  let pair = #(1, 2)
+  case x {
+    1 -> {
+      xs
+    }
+    _ -> xs
+  }
+}
+"#,
+            expect![[r#"
+                Comment 0..32: //// A module .. //// about shapes.
+                Imports 34..89: import gleam/int .. import gleam/string
+                Comment 91..123: /// A shape. .. /// With two lines.
+                Adt 139..174: { .. }
+                Comment 176..208: // Plain comment .. // on two lines
+                Block 221..329: { .. }
+                List 234..253: [ ..   ]
+                Case 277..327: case x { ..   }
+                Clause 290..311: 1 -> { ..     }
+                Block 295..311: { ..     }
+            "#]],
+        );
+    }
+}
@@ -83,6 +83,14 @@ mod tests {
         check_no("fn wops() { case 1 { a -> a} $0a}")
     }
 
+    #[test]
+    fn bit_array_segment_size() {
+        check(
+            "fn main(x) { let <<len:8, data:bytes-size($0len)>> = x }",
+            expect!["<len>:8"],
+        );
+    }
+
     #[test]
     fn pattern_variant() {
         check("fn wops() { case Bla(1) { Bla(a) -> $0a} }", expect!["<a>"]);
@@ -0,0 +1,268 @@
+use std::path::{Component, Path, PathBuf};
+
+use syntax::ast::{self, AstNode};
+use syntax::best_token_at_offset;
+
+use super::NavigationTarget;
+
+use crate::def::hir::{Adt, Function, TypeAlias};
+use crate::def::semantics::{self, Definition};
+use crate::def::Semantics;
+use crate::ty::{Ty, TyDatabase};
+use crate::{FilePos, Target, VfsPath};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GotoTypeDefinitionResult {
+    pub targets: Vec<NavigationTarget>,
+    /// Native files an `@external` function may be implemented in. They are not part of the VFS,
+    /// so only the ones that exist on disk should be offered.
+    pub external_files: Vec<VfsPath>,
+}
+
+pub(crate) fn goto_type_definition(
+    db: &dyn TyDatabase,
+    FilePos { file_id, pos }: FilePos,
+) -> Option<GotoTypeDefinitionResult> {
+    let sema = Semantics::new(db);
+    let parse = sema.parse(file_id);
+    let tok = best_token_at_offset(parse.syntax(), pos)?;
+    let node = tok.parent()?;
+
+    let mut defs = Vec::new();
+    let mut external_files = Vec::new();
+    match semantics::classify_node(&sema, &node) {
+        Some(Definition::Adt(it)) => defs.push(Definition::Adt(it)),
+        Some(Definition::TypeAlias(it)) => defs.push(Definition::TypeAlias(it)),
+        Some(Definition::Variant(it)) => defs.push(Definition::Adt(it.parent())),
+        Some(Definition::Function(it)) => {
+            type_defs(&it.ty(db), &mut defs);
+            external_files = externals(db, it);
+        }
+        Some(Definition::ModuleConstant(it)) => type_defs(&it.ty(db), &mut defs),
+        Some(Definition::Local(it)) => type_defs(&it.ty(db), &mut defs),
+        // Fields and everything else go by the type of the surrounding expression.
+        _ => {
+            let expr = node.ancestors().find_map(ast::Expr::cast)?;
+            let ty = sema.analyze(expr.syntax())?.type_of_expr(&expr)?;
+            type_defs(&ty, &mut defs);
+        }
+    }
+
+    let targets = defs
+        .iter()
+        .filter_map(|def| def.to_nav(db))
+        .collect::<Vec<_>>();
+    if targets.is_empty() && external_files.is_empty() {
+        return None;
+    }
+    Some(GotoTypeDefinitionResult {
+        targets,
+        external_files,
+    })
+}
+
+/// Every user defined type `ty` mentions, `Result(User, Error)` gives both `User` and `Error`.
+fn type_defs(ty: &Ty, defs: &mut Vec<Definition>) {
+    match ty {
+        Ty::Adt { adt_id, params } => {
+            let def = Definition::Adt(Adt::from(*adt_id));
+            if !defs.contains(&def) {
+                defs.push(def);
+            }
+            params.iter().for_each(|ty| type_defs(ty, defs));
+        }
+        Ty::Alias {
+            alias_id, params, ..
+        } => {
+            let def = Definition::TypeAlias(TypeAlias::from(*alias_id));
+            if !defs.contains(&def) {
+                defs.push(def);
+            }
+            params.iter().for_each(|ty| type_defs(ty, defs));
+        }
+        Ty::Result { ok, err } => {
+            type_defs(ok, defs);
+            type_defs(err, defs);
+        }
+        Ty::List { of } => type_defs(of, defs),
+        Ty::Function { params, return_ } => {
+            params.iter().for_each(|(_, ty)| type_defs(ty, defs));
+            type_defs(return_, defs);
+        }
+        Ty::Tuple { fields } => fields.iter().for_each(|ty| type_defs(ty, defs)),
+        Ty::Unknown
+        | Ty::Hole
+        | Ty::Generic { .. }
+        | Ty::Nil
+        | Ty::Bool
+        | Ty::Int
+        | Ty::Float
+        | Ty::String
+        | Ty::BitArray
+        | Ty::UtfCodepoint => {}
+    }
+}
+
+/// Where the `@external` implementations of `func` would live. Erlang modules are looked up next
+/// to the Gleam module and at the top of `src`, JavaScript paths are relative to the Gleam module.
+fn externals(db: &dyn TyDatabase, func: Function) -> Vec<VfsPath> {
+    let file = func.module(db.upcast()).id;
+    let source_root = db.source_root(db.file_source_root(file));
+    let Some(module_dir) = source_root
+        .path_for_file(file)
+        .as_path()
+        .and_then(Path::parent)
+    else {
+        return Vec::new();
+    };
+    let src_dir = source_root.root_path().join("src");
+
+    let mut paths = Vec::new();
+    for external in func.externals(db.upcast()) {
+        let candidates = match external.target {
+            Target::Erlang => {
+                let file_name = format!("{}.erl", external.module);
+                vec![module_dir.join(&file_name), src_dir.join(&file_name)]
+            }
+            // Anything else is a package from `node_modules`.
+            Target::Javascript
+                if external.module.starts_with("./") || external.module.starts_with("../") =>
+            {
+                vec![normalize(&module_dir.join(external.module.as_str()))]
+            }
+            Target::Javascript => Vec::new(),
+        };
+        for path in candidates.into_iter().map(VfsPath::Path) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Resolves `.` and `..` without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::base::SourceDatabase;
+    use crate::tests::TestDB;
+
+    use super::goto_type_definition;
+
+    #[track_caller]
+    fn check(fixture: &str, expect: Expect) {
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        assert_eq!(f.markers().len(), 1, "Missing markers");
+        let result = goto_type_definition(&db, f[0]).expect("No type definition");
+        let mut got = String::new();
+        for target in result.targets {
+            let src = db.file_content(target.file_id);
+            got += &format!("{}\n", &src[target.focus_range]);
+        }
+        for path in result.external_files {
+            got += &format!("file://{}\n", path.display());
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[test]
+    fn local_variable() {
+        check(
+            r#"
+type User { User(name: String) }
+fn main() {
+  let user = User("Lucy")
+  $0user
+}
+"#,
+            expect![[r#"
+                User
+            "#]],
+        );
+    }
+
+    #[test]
+    fn multiple_targets() {
+        check(
+            r#"
+type User { User(name: String) }
+type Error { NotFound }
+type Lookup = Result(User, Error)
+fn find() -> Result(User, Error) { todo }
+fn main() {
+  let x: Lookup = find()
+  #(f$0ind(), x)
+}
+"#,
+            expect![[r#"
+                User
+                Error
+            "#]],
+        );
+    }
+
+    #[test]
+    fn alias() {
+        check(
+            r#"
+type Pair(a) = #(a, a)
+fn main(p: Pair(Int)) { $0p }
+"#,
+            expect![[r#"
+                Pair
+            "#]],
+        );
+    }
+
+    #[test]
+    fn field_access() {
+        check(
+            r#"
+type Name { Name(String) }
+type User { User(name: Name) }
+fn main(u: User) { u.na$0me }
+"#,
+            expect![[r#"
+                Name
+            "#]],
+        );
+    }
+
+    #[test]
+    fn externals() {
+        check(
+            r#"
+#- app/ffi.gleam
+type Handle { Handle }
+@external(erlang, "app_ffi", "open")
+@external(javascript, "../ffi.mjs", "open")
+pub fn open() -> Handle
+@external(javascript, "node:fs", "close")
+pub fn close() -> Nil
+fn main() { op$0en() close() }
+"#,
+            expect![[r#"
+                Handle
+                file:///test/app/app_ffi.erl
+                file:///src/app_ffi.erl
+                file:///test/ffi.mjs
+            "#]],
+        );
+    }
+}
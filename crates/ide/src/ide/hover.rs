@@ -1,4 +1,4 @@
-use crate::def::hir::Function;
+use crate::def::hir::{Function, ModuleDef};
 use crate::ty;
 
 use syntax::ast::{self, AstNode};
@@ -43,14 +43,14 @@ pub(crate) fn hover(db: &dyn TyDatabase, FilePos { file_id, pos }: FilePos) -> O
         let ty = analyzer.type_of_expr(&ast::Expr::from(it))?;
         return Some(HoverResult {
             range: tok.text_range(),
-            markup: format!("```gleam\n{}\n```", ty.display(db)),
+            markup: format!("```gleam\n{}\n```", ty.display(db).keep_aliases()),
         });
     };
 
     match semantics::classify_node(&sema, &tok.parent()?)? {
-        semantics::Definition::Adt(it) => return render_adt(db, tok, it),
-        semantics::Definition::Function(it) => return render_function(db, tok, it),
-        semantics::Definition::Variant(it) => return render_variant(db, tok, it),
+        semantics::Definition::Adt(it) => render_adt(db, tok, it),
+        semantics::Definition::Function(it) => render_function(db, tok, it),
+        semantics::Definition::Variant(it) => render_variant(db, tok, it),
         semantics::Definition::ModuleConstant(it) => render_constant(db, tok, it),
         semantics::Definition::Field(it) => {
             let ty = it.ty(db.upcast());
@@ -63,7 +63,7 @@ pub(crate) fn hover(db: &dyn TyDatabase, FilePos { file_id, pos }: FilePos) -> O
             let ty = it.ty(db);
             Some(HoverResult {
                 range: tok.text_range(),
-                markup: format!("```gleam\n{}\n```", ty.display(db)),
+                markup: format!("```gleam\n{}\n```", ty.display(db).keep_aliases()),
             })
         }
         semantics::Definition::Module(it) => {
@@ -80,7 +80,7 @@ pub(crate) fn hover(db: &dyn TyDatabase, FilePos { file_id, pos }: FilePos) -> O
             range: tok.text_range(),
             markup: format!("```gleam\n{:?}\n```", it),
         }),
-        semantics::Definition::TypeAlias(it) => return render_type_alias(db, tok, it),
+        semantics::Definition::TypeAlias(it) => render_type_alias(db, tok, it),
     }
 }
 
@@ -89,7 +89,7 @@ fn render_adt(
     tok: syntax::rowan::SyntaxToken<syntax::GleamLanguage>,
     it: crate::def::hir::Adt,
 ) -> Option<HoverResult> {
-    let docs = it.docs(db.upcast());
+    let docs = deprecation(db, ModuleDef::Adt(it)) + &it.docs(db.upcast());
     Some(HoverResult {
         range: tok.text_range(),
         markup: format!("```gleam\ntype {}\n```\n___\n{docs}", it.name(db.upcast())),
@@ -101,10 +101,14 @@ fn render_constant(
     tok: syntax::rowan::SyntaxToken<syntax::GleamLanguage>,
     it: crate::def::hir::ModuleConstant,
 ) -> Option<HoverResult> {
-    let docs = it.docs(db.upcast());
+    let docs = deprecation(db, ModuleDef::ModuleConstant(it)) + &it.docs(db.upcast());
     Some(HoverResult {
         range: tok.text_range(),
-        markup: format!("```gleam\nconst {}\n```\n___\n{docs}", it.name(db.upcast())),
+        markup: format!(
+            "```gleam\nconst {}: {}\n```\n___\n{docs}",
+            it.name(db.upcast()),
+            it.ty(db).display(db).keep_aliases()
+        ),
     })
 }
 
@@ -113,9 +117,14 @@ fn render_type_alias(
     tok: syntax::rowan::SyntaxToken<syntax::GleamLanguage>,
     it: crate::def::hir::TypeAlias,
 ) -> Option<HoverResult> {
+    let mut markup = format!("```gleam\ntype {}\n```", it.name(db.upcast()));
+    let deprecation = deprecation(db, ModuleDef::TypeAlias(it));
+    if !deprecation.is_empty() {
+        markup = format!("{markup}\n___\n{deprecation}");
+    }
     Some(HoverResult {
         range: tok.text_range(),
-        markup: format!("```gleam\ntype {}\n```", it.name(db.upcast())),
+        markup,
     })
 }
 
@@ -124,7 +133,7 @@ fn render_variant(
     tok: syntax::rowan::SyntaxToken<syntax::GleamLanguage>,
     it: crate::def::hir::Variant,
 ) -> Option<HoverResult> {
-    let docs = it.docs(db.upcast());
+    let docs = deprecation(db, ModuleDef::Variant(it)) + &it.docs(db.upcast());
     Some(HoverResult {
         range: tok.text_range(),
         markup: format!("```gleam\n{}\n```\n___\n{docs}", it.name(db.upcast())),
@@ -137,12 +146,12 @@ fn render_function(
     it: Function,
 ) -> Option<HoverResult> {
     let name = it.name(db.upcast());
-    let docs = it.docs(db.upcast());
-    match it.ty(db) {
+    let docs = deprecation(db, ModuleDef::Function(it)) + &it.docs(db.upcast());
+    match it.ty(db).unalias() {
         ty::Ty::Function { params, return_ } => {
             let params = params
                 .iter()
-                .map(|(_, ty)| format!("{}", ty.display(db)))
+                .map(|(_, ty)| format!("{}", ty.display(db).keep_aliases()))
                 .collect::<Vec<_>>()
                 .join(", ");
             Some(HoverResult {
@@ -151,7 +160,7 @@ fn render_function(
                     "```gleam\nfn {}({}) -> {}\n```\n___\n{docs}",
                     name,
                     params,
-                    return_.display(db)
+                    return_.display(db).keep_aliases()
                 ),
             })
         }
@@ -159,6 +168,15 @@ fn render_function(
     }
 }
 
+/// A note shown above the docs of deprecated items.
+fn deprecation(db: &dyn TyDatabase, def: ModuleDef) -> String {
+    match def.attrs(db.upcast()).deprecated {
+        Some(message) if message.is_empty() => "**Deprecated**\n\n".into(),
+        Some(message) => format!("**Deprecated**: {message}\n\n"),
+        None => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::base::SourceDatabase;
@@ -201,6 +219,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deprecated() {
+        check(
+            r#"
+/// Adds things.
+@deprecated("Use `add` instead")
+fn plus(a: Int, b: Int) -> Int { a + b }
+fn main() { $0plus(1, 2) }"#,
+            "plus",
+            expect![[r#"
+                ```gleam
+                fn plus(Int, Int) -> Int
+                ```
+                ___
+                **Deprecated**: Use `add` instead
+
+                 Adds things.
+            "#]],
+        );
+        check(
+            r#"
+@deprecated("Use `Int` instead")
+type Number = Int
+fn main(n: $0Number) { n }"#,
+            "Number",
+            expect![[r#"
+                ```gleam
+                type Number
+                ```
+                ___
+                **Deprecated**: Use `Int` instead
+            "#]],
+        );
+    }
+
     #[test]
     fn generic_field() {
         check(
@@ -221,7 +274,7 @@ mod tests {
             "big",
             expect![[r#"
             ```gleam
-            const big
+            const big: Int
             ```
             ___
             Very large constant
@@ -229,6 +282,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn alias_name() {
+        check(
+            r#"
+type Handler(a) = fn(String) -> Result(a, Nil)
+fn run(h: Handler(Int)) { $0h }"#,
+            "h",
+            expect![[r#"
+                ```gleam
+                Handler(Int)
+                ```
+            "#]],
+        );
+    }
+
+    #[test]
+    fn alias_stays_on_annotation() {
+        check(
+            r#"
+type UserId = Int
+fn f(id: UserId) {
+    let next = id + 1
+    $0next
+}"#,
+            "next",
+            expect![[r#"
+                ```gleam
+                Int
+                ```
+            "#]],
+        );
+        check(
+            r#"
+type UserId = Int
+fn f(id: UserId) { id + 1 }
+fn main() { $0f(1) }"#,
+            "f",
+            expect![[r#"
+                ```gleam
+                fn f(UserId) -> Int
+                ```
+                ___
+            "#]],
+        );
+    }
+
     #[test]
     fn pattern_variant() {
         check(
@@ -252,6 +351,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn use_binding() {
+        check(
+            r#"
+            fn try(result: Result(a, e), next: fn(a) -> Result(b, e)) -> Result(b, e) {
+                todo
+            }
+
+            fn main(r: Result(Int, String)) {
+                use value <- try(r)
+                Ok($0value)
+            }
+            "#,
+            "value",
+            expect![[r#"
+                ```gleam
+                Int
+                ```
+            "#]],
+        );
+    }
+
     #[test]
     fn adt_fields() {
         check(
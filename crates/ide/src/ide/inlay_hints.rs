@@ -0,0 +1,481 @@
+use std::collections::HashSet;
+
+use smol_str::SmolStr;
+use syntax::{SyntaxNode, TextRange, TextSize};
+
+use crate::def::body::{Body, BodySourceMap};
+use crate::def::hir::ModuleDef;
+use crate::def::hir_def::FunctionLoc;
+use crate::def::module::{Expr, ExprId, Pattern, PatternId, Statement};
+use crate::def::resolver_for_expr;
+use crate::def::semantics::referenced_def;
+use crate::ty::display::TyDisplay;
+use crate::ty::{InferenceResult, Ty, TyDatabase};
+use crate::FileId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InlayHintsConfig {
+    /// `let` and `use` bindings without an annotation.
+    pub binding_types: bool,
+    /// Anonymous function parameters without an annotation.
+    pub lambda_param_types: bool,
+    /// The value at the end of each line of a multi-line pipe.
+    pub pipe_steps: bool,
+    /// The parameter a positional call argument is passed to.
+    pub parameter_names: bool,
+}
+
+impl Default for InlayHintsConfig {
+    fn default() -> Self {
+        Self {
+            binding_types: true,
+            lambda_param_types: true,
+            pipe_steps: true,
+            parameter_names: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InlayHintKind {
+    /// The type of a binding, shown after it.
+    Type,
+    /// The type of a pipe step, shown at the end of its line.
+    PipeStep,
+    /// The parameter name, shown before the argument.
+    Parameter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlayHint {
+    pub pos: TextSize,
+    pub kind: InlayHintKind,
+    pub label: String,
+}
+
+pub(crate) fn inlay_hints(
+    db: &dyn TyDatabase,
+    file: FileId,
+    range: Option<TextRange>,
+    config: &InlayHintsConfig,
+) -> Vec<InlayHint> {
+    let root = db.parse(file).syntax_node();
+    let items = db.module_items(file);
+    let mut hints = Vec::new();
+    for (func_idx, _) in items.functions() {
+        let fn_id = db.intern_function(FunctionLoc {
+            file_id: file,
+            value: func_idx,
+        });
+        let infer = db.infer_function(fn_id);
+        let (body, source_map) = db.body_with_source_map(fn_id);
+        let mut ctx = HintCtx {
+            db,
+            root: &root,
+            body: &body,
+            source_map: &source_map,
+            infer: &infer,
+            hints: &mut hints,
+        };
+        let resolver = |expr| resolver_for_expr(db.upcast(), fn_id, expr);
+
+        // Calls whose first or last parameter is taken by a pipe or a `use` callback.
+        let mut piped_calls = HashSet::new();
+        let mut use_calls = HashSet::new();
+        for (_, expr) in body.exprs() {
+            match expr {
+                Expr::Block { stmts } => {
+                    for stmt in stmts {
+                        match stmt {
+                            Statement::Let {
+                                pattern,
+                                annotation: None,
+                                ..
+                            } if config.binding_types => ctx.binding_hints(*pattern),
+                            Statement::Use { patterns, expr } => {
+                                use_calls.insert(*expr);
+                                if config.binding_types {
+                                    for (pattern, _) in patterns.iter().filter(|(_, a)| a.is_none())
+                                    {
+                                        ctx.binding_hints(*pattern);
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Expr::Lambda { params, .. } if config.lambda_param_types => {
+                    for (pattern, _) in params.iter().filter(|(_, a)| a.is_none()) {
+                        ctx.binding_hints(*pattern);
+                    }
+                }
+                Expr::Pipe { left, right } => {
+                    if let Expr::Call { func, args } = &body[*right] {
+                        if inserts_piped_arg(&body, &infer.ty_for_expr(*func), args) {
+                            piped_calls.insert(*right);
+                        }
+                    }
+                    if config.pipe_steps {
+                        ctx.pipe_step_hint(*left, *right);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if config.parameter_names {
+            for (call, expr) in body.exprs() {
+                let Expr::Call { func, args } = expr else {
+                    continue;
+                };
+                let callee = referenced_def(&body, &infer, &resolver, *func);
+                let params = param_names(db, callee, &infer.ty_for_expr(*func));
+                let slots = param_slots(
+                    &params,
+                    args,
+                    piped_calls.contains(&call),
+                    use_calls.contains(&call),
+                );
+                for ((label, arg), slot) in args.iter().zip(slots) {
+                    if label.is_some() {
+                        continue;
+                    }
+                    let Some(name) = slot.and_then(|slot| params[slot].as_ref()) else {
+                        continue;
+                    };
+                    ctx.parameter_hint(*arg, name);
+                }
+            }
+        }
+    }
+
+    if let Some(range) = range {
+        hints.retain(|hint| range.contains_inclusive(hint.pos));
+    }
+    hints.sort_by_key(|hint| hint.pos);
+    hints
+}
+
+struct HintCtx<'a> {
+    db: &'a dyn TyDatabase,
+    root: &'a SyntaxNode,
+    body: &'a Body,
+    source_map: &'a BodySourceMap,
+    infer: &'a InferenceResult,
+    hints: &'a mut Vec<InlayHint>,
+}
+
+impl HintCtx<'_> {
+    /// Every variable bound by `pattern`, `let #(a, b) = ..` hints both `a` and `b`.
+    fn binding_hints(&mut self, pattern: PatternId) {
+        match &self.body[pattern] {
+            Pattern::Variable { .. } | Pattern::Spread { name: Some(_) } => {
+                let ty = self.infer.ty_for_pattern(pattern);
+                let Some(src) = self.source_map.node_for_pattern(pattern) else {
+                    return;
+                };
+                if ty != Ty::Unknown {
+                    self.hints.push(InlayHint {
+                        pos: src.value.text_range().end(),
+                        kind: InlayHintKind::Type,
+                        label: format!(": {}", ty.display(self.db).keep_aliases()),
+                    });
+                }
+            }
+            Pattern::Tuple { fields } => fields.iter().for_each(|&p| self.binding_hints(p)),
+            Pattern::List { elements } => elements.iter().for_each(|&p| self.binding_hints(p)),
+            Pattern::VariantRef { fields, .. } => {
+                fields.iter().for_each(|&(_, p)| self.binding_hints(p))
+            }
+            // Every alternative binds the same names.
+            Pattern::AlternativePattern { patterns } => {
+                if let Some(&first) = patterns.first() {
+                    self.binding_hints(first);
+                }
+            }
+            Pattern::AsPattern { pattern, as_name } => {
+                self.binding_hints(*pattern);
+                if let Some(as_name) = as_name {
+                    self.binding_hints(*as_name);
+                }
+            }
+            Pattern::Concat { pattern } => self.binding_hints(*pattern),
+            Pattern::BitArray { segments } => {
+                segments.iter().for_each(|s| self.binding_hints(s.value))
+            }
+            Pattern::Missing
+            | Pattern::Hole
+            | Pattern::Literal { .. }
+            | Pattern::Spread { name: None } => {}
+        }
+    }
+
+    /// Pipe steps followed by a line break show their value at the end of the line.
+    fn pipe_step_hint(&mut self, left: ExprId, right: ExprId) {
+        let (Some(left_src), Some(right_src)) = (
+            self.source_map.node_for_expr(left),
+            self.source_map.node_for_expr(right),
+        ) else {
+            return;
+        };
+        let end = left_src.value.text_range().end();
+        let start = right_src.value.text_range().start();
+        if start < end
+            || !self
+                .root
+                .text()
+                .slice(TextRange::new(end, start))
+                .contains_char('\n')
+        {
+            return;
+        }
+        let ty = self.infer.ty_for_expr(left);
+        if ty != Ty::Unknown {
+            self.hints.push(InlayHint {
+                pos: end,
+                kind: InlayHintKind::PipeStep,
+                label: ty.display(self.db).keep_aliases().to_string(),
+            });
+        }
+    }
+
+    fn parameter_hint(&mut self, arg: ExprId, name: &SmolStr) {
+        // `f(name)` and `f(_)` say enough already.
+        match &self.body[arg] {
+            Expr::Variable(var) if var == name => return,
+            Expr::Hole => return,
+            _ => {}
+        }
+        let Some(src) = self.source_map.node_for_expr(arg) else {
+            return;
+        };
+        self.hints.push(InlayHint {
+            pos: src.value.text_range().start(),
+            kind: InlayHintKind::Parameter,
+            label: format!("{name}:"),
+        });
+    }
+}
+
+/// Mirrors type inference: `a |> f(b)` passes `a` first unless `f` is known to take exactly the
+/// arguments given or is a capture.
+fn inserts_piped_arg(body: &Body, func_ty: &Ty, args: &[(Option<SmolStr>, ExprId)]) -> bool {
+    let is_capture = args.iter().any(|(_, arg)| matches!(body[*arg], Expr::Hole));
+    !is_capture
+        && !matches!(func_ty.unalias(), Ty::Function { params, .. } if params.len() == args.len())
+}
+
+/// What each parameter of the callee is called, preferring labels over parameter names.
+fn param_names(db: &dyn TyDatabase, callee: Option<ModuleDef>, ty: &Ty) -> Vec<Option<SmolStr>> {
+    match callee {
+        Some(ModuleDef::Function(func)) => func
+            .params(db.upcast())
+            .into_iter()
+            .map(|param| Some(param.label.unwrap_or(param.name)))
+            .collect(),
+        Some(ModuleDef::Variant(variant)) => variant
+            .fields(db.upcast())
+            .into_iter()
+            .map(|field| field.label(db.upcast()))
+            .collect(),
+        _ => match ty.unalias() {
+            Ty::Function { params, .. } => params.iter().map(|(label, _)| label.clone()).collect(),
+            _ => Vec::new(),
+        },
+    }
+}
+
+/// The parameter each argument ends up in. Labelled arguments take their slot, the piped value
+/// the first free one and a `use` callback the last, positional arguments fill the rest in order.
+fn param_slots(
+    params: &[Option<SmolStr>],
+    args: &[(Option<SmolStr>, ExprId)],
+    piped: bool,
+    trailing: bool,
+) -> Vec<Option<usize>> {
+    let count = params.len();
+    let mut taken = vec![false; count];
+    let mut slots = vec![None; args.len()];
+    for (slot, (label, _)) in slots.iter_mut().zip(args) {
+        let Some(label) = label else {
+            continue;
+        };
+        if let Some(idx) = params.iter().position(|p| p.as_ref() == Some(label)) {
+            if !taken[idx] {
+                taken[idx] = true;
+                *slot = Some(idx);
+            }
+        }
+    }
+    if piped {
+        if let Some(first) = taken.iter().position(|t| !t) {
+            taken[first] = true;
+        }
+    }
+    if trailing {
+        if let Some(last) = taken.iter().rposition(|t| !t) {
+            taken[last] = true;
+        }
+    }
+    let mut free = (0..count).filter(|&idx| !taken[idx]);
+    for (slot, (label, _)) in slots.iter_mut().zip(args) {
+        if label.is_none() {
+            *slot = free.next();
+        }
+    }
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::TestDB;
+    use crate::SourceDatabase;
+
+    use super::{InlayHintKind, InlayHintsConfig};
+
+    #[track_caller]
+    fn check_with(fixture: &str, config: InlayHintsConfig, expect: Expect) {
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        let file = f.files()[0];
+        let mut text = db.file_content(file).to_string();
+        // Insert from the back so earlier positions stay valid.
+        for hint in super::inlay_hints(&db, file, None, &config).iter().rev() {
+            let pos = usize::from(hint.pos);
+            let rendered = match hint.kind {
+                InlayHintKind::Type => format!("⟨{}⟩", hint.label),
+                InlayHintKind::PipeStep => format!(" ⟨{}⟩", hint.label),
+                InlayHintKind::Parameter => format!("⟨{}⟩ ", hint.label),
+            };
+            text.insert_str(pos, &rendered);
+        }
+        expect.assert_eq(&text);
+    }
+
+    #[track_caller]
+    fn check(fixture: &str, expect: Expect) {
+        check_with(fixture, InlayHintsConfig::default(), expect);
+    }
+
+    #[test]
+    fn binding_types() {
+        check(
+            r#"
+fn main() {
+  let x = 1
+  let #(a, b) = #("a", 1.0)
+  let y: Int = x
+  let id = fn(v) { v }
+  let typed = fn(v: Int) { v }
+  use z <- apply(2)
+  z
+}
+fn apply(x: Int, f: fn(Int) -> a) -> a { f(x) }
+"#,
+            expect![[r#"
+                fn main() {
+                  let x⟨: Int⟩ = 1
+                  let #(a⟨: String⟩, b⟨: Float⟩) = #("a", 1.0)
+                  let y: Int = x
+                  let id⟨: fn(a) -> a⟩ = fn(v⟨: a⟩) { v }
+                  let typed⟨: fn(Int) -> Int⟩ = fn(v: Int) { v }
+                  use z⟨: Int⟩ <- apply(⟨x:⟩ 2)
+                  z
+                }
+                fn apply(x: Int, f: fn(Int) -> a) -> a { f(x) }"#]],
+        );
+    }
+
+    #[test]
+    fn pipe_steps() {
+        check(
+            r#"
+fn main() {
+  [1, 2]
+  |> map(fn(x) { x * 2 })
+  |> length
+  |> to_string
+}
+fn short() { 1 |> to_string }
+fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }
+fn length(list: List(a)) -> Int { todo }
+fn to_string(x: Int) -> String { todo }
+"#,
+            expect![[r#"
+                fn main() {
+                  [1, 2] ⟨List(Int)⟩
+                  |> map(⟨f:⟩ fn(x⟨: Int⟩) { x * 2 }) ⟨List(Int)⟩
+                  |> length ⟨Int⟩
+                  |> to_string
+                }
+                fn short() { 1 |> to_string }
+                fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }
+                fn length(list: List(a)) -> Int { todo }
+                fn to_string(x: Int) -> String { todo }"#]],
+        );
+    }
+
+    #[test]
+    fn parameter_names() {
+        check(
+            r#"
+fn main(list) {
+  range(0, to: 10)
+  replace("abc", each: "a", with: "b")
+  list |> fold(0, add)
+  use acc, x <- fold(list, 0)
+  Point(1, y: 2)
+  range(list, 1)
+}
+fn range(from: Int, to to: Int) { todo }
+fn replace(in string: String, each pattern: String, with replacement: String) { todo }
+fn fold(over list: List(Int), from initial: Int, with f: fn(Int, Int) -> Int) { todo }
+fn add(a: Int, b: Int) -> Int { a + b }
+type Point { Point(x: Int, y: Int) }
+"#,
+            expect![[r#"
+                fn main(list) {
+                  range(⟨from:⟩ 0, to: 10)
+                  replace(⟨in:⟩ "abc", each: "a", with: "b")
+                  list |> fold(⟨from:⟩ 0, ⟨with:⟩ add)
+                  use acc⟨: Int⟩, x⟨: Int⟩ <- fold(⟨over:⟩ list, ⟨from:⟩ 0)
+                  Point(⟨x:⟩ 1, y: 2)
+                  range(⟨from:⟩ list, ⟨to:⟩ 1)
+                }
+                fn range(from: Int, to to: Int) { todo }
+                fn replace(in string: String, each pattern: String, with replacement: String) { todo }
+                fn fold(over list: List(Int), from initial: Int, with f: fn(Int, Int) -> Int) { todo }
+                fn add(a: Int, b: Int) -> Int { a + b }
+                type Point { Point(x: Int, y: Int) }"#]],
+        );
+    }
+
+    #[test]
+    fn toggles() {
+        check_with(
+            r#"
+fn main() {
+  let x = fn(v) { v }
+  add(1, 2)
+  |> add(3)
+}
+fn add(a: Int, b: Int) -> Int { a + b }
+"#,
+            InlayHintsConfig {
+                binding_types: false,
+                lambda_param_types: true,
+                pipe_steps: false,
+                parameter_names: false,
+            },
+            expect![[r#"
+                fn main() {
+                  let x = fn(v⟨: a⟩) { v }
+                  add(1, 2)
+                  |> add(3)
+                }
+                fn add(a: Int, b: Int) -> Int { a + b }"#]],
+        );
+    }
+}
@@ -1,13 +1,21 @@
+mod assists;
+mod call_hierarchy;
 mod completion;
 mod diagnostics;
+mod folding_ranges;
 mod goto_definition;
+mod goto_type_definition;
 mod highlight_related;
 mod hover;
+mod inlay_hints;
 mod references;
 mod rename;
+mod selection_ranges;
 mod semantic_highlighting;
 mod signature_help;
+mod symbols;
 mod syntax_tree;
+mod todos;
 
 use crate::base::SourceDatabaseStorage;
 use crate::def::{DefDatabaseStorage, InternDatabaseStorage};
@@ -20,14 +28,21 @@ use crate::{
 use salsa::{Database, Durability, ParallelDatabase};
 use smol_str::SmolStr;
 use std::fmt;
-use syntax::TextRange;
+use syntax::{TextRange, TextSize};
 
+pub use assists::{Assist, AssistKind};
+pub use call_hierarchy::{CallHierarchyItem, IncomingCall, OutgoingCall};
 pub use completion::{CompletionItem, CompletionItemKind, CompletionRelevance};
+pub use folding_ranges::{Fold, FoldKind};
 pub use goto_definition::GotoDefinitionResult;
+pub use goto_type_definition::GotoTypeDefinitionResult;
 pub use highlight_related::HlRelated;
 pub use hover::HoverResult;
+pub use inlay_hints::{InlayHint, InlayHintKind, InlayHintsConfig};
 pub use semantic_highlighting::{HlRange, HlTag};
 pub use signature_help::SignatureHelp;
+pub use symbols::{DocumentSymbol, WorkspaceSymbol};
+pub use todos::Todo;
 
 pub const DEFAULT_LRU_CAP: usize = 128;
 
@@ -183,6 +198,13 @@ impl Analysis {
         self.with_db(|db| goto_definition::goto_definition(db, pos))
     }
 
+    pub fn goto_type_definition(
+        &self,
+        pos: FilePos,
+    ) -> Cancellable<Option<GotoTypeDefinitionResult>> {
+        self.with_db(|db| goto_type_definition::goto_type_definition(db, pos))
+    }
+
     pub fn references(&self, pos: FilePos) -> Cancellable<Option<Vec<FileRange>>> {
         self.with_db(|db| references::references(db, pos))
     }
@@ -199,6 +221,59 @@ impl Analysis {
         self.with_db(|db| rename::rename(db, fpos, new_name))
     }
 
+    pub fn prepare_call_hierarchy(&self, fpos: FilePos) -> Cancellable<Option<CallHierarchyItem>> {
+        self.with_db(|db| call_hierarchy::prepare_call_hierarchy(db, fpos))
+    }
+
+    pub fn incoming_calls(&self, fpos: FilePos) -> Cancellable<Option<Vec<IncomingCall>>> {
+        self.with_db(|db| call_hierarchy::incoming_calls(db, fpos))
+    }
+
+    pub fn outgoing_calls(&self, fpos: FilePos) -> Cancellable<Option<Vec<OutgoingCall>>> {
+        self.with_db(|db| call_hierarchy::outgoing_calls(db, fpos))
+    }
+
+    pub fn folding_ranges(&self, file: FileId) -> Cancellable<Vec<Fold>> {
+        self.with_db(|db| folding_ranges::folding_ranges(db, file))
+    }
+
+    pub fn selection_ranges(
+        &self,
+        file: FileId,
+        positions: Vec<TextSize>,
+    ) -> Cancellable<Vec<Vec<TextRange>>> {
+        self.with_db(|db| selection_ranges::selection_ranges(db, file, positions))
+    }
+
+    pub fn code_actions(&self, frange: FileRange) -> Cancellable<Vec<Assist>> {
+        self.with_db(|db| assists::code_actions(db, frange))
+    }
+
+    pub fn inlay_hints(
+        &self,
+        file: FileId,
+        range: Option<TextRange>,
+        config: InlayHintsConfig,
+    ) -> Cancellable<Vec<InlayHint>> {
+        self.with_db(|db| inlay_hints::inlay_hints(db, file, range, &config))
+    }
+
+    pub fn document_symbols(&self, file_id: FileId) -> Cancellable<Vec<DocumentSymbol>> {
+        self.with_db(|db| symbols::document_symbols(db, file_id))
+    }
+
+    pub fn workspace_symbols(
+        &self,
+        query: &str,
+        include_dependencies: bool,
+    ) -> Cancellable<Vec<WorkspaceSymbol>> {
+        self.with_db(|db| symbols::workspace_symbols(db, query, include_dependencies))
+    }
+
+    pub fn todos(&self) -> Cancellable<Vec<Todo>> {
+        self.with_db(|db| todos::todos(db))
+    }
+
     pub fn syntax_tree(&self, file_id: FileId) -> Cancellable<String> {
         self.with_db(|db| syntax_tree::syntax_tree(db, file_id))
     }
@@ -57,11 +57,11 @@ mod tests {
 
         let mut actual = String::new();
         for (id, mut ranges) in file_set.into_iter().sorted_by(|a, b| a.0.cmp(&b.0)) {
-            ranges.sort_by(|a, b| b.start().cmp(&a.start()));
+            ranges.sort_by_key(|r| std::cmp::Reverse(r.start()));
             let mut content = db.file_content(id).to_string();
             for range in ranges {
-                content.insert_str(usize::from(range.end()), ">");
-                content.insert_str(usize::from(range.start()), "<");
+                content.insert(usize::from(range.end()), '>');
+                content.insert(usize::from(range.start()), '<');
             }
 
             actual += &format!("--- {:?}", id);
@@ -146,4 +146,27 @@ fn rename_test() { <test> }
             ],
         );
     }
+
+    #[test]
+    fn case_guard() {
+        check(
+            r#"
+fn main(a, $0limit) {
+    case a {
+        b if b > limit -> limit
+        _ -> 0
+    }
+}"#,
+            expect![[r#"
+                --- FileId(0)
+
+                fn main(a, <limit>) {
+                    case a {
+                        b if b > <limit> -> <limit>
+                        _ -> 0
+                    }
+                }
+            "#]],
+        );
+    }
 }
@@ -42,7 +42,7 @@ pub(crate) fn prepare_rename(
         Definition::Module(_) | Definition::BuiltIn(_) => {
             return Err(String::from("No references found"))
         }
-        _ => def.name(db.upcast()).ok_or_else(|| "No references found")?,
+        _ => def.name(db.upcast()).ok_or("No references found")?,
     };
 
     Ok((range, name))
@@ -62,8 +62,8 @@ pub(crate) fn rename(
 
     let mut lexer = GleamLexer::new(new_name);
 
-    let new_token = lexer.next().ok_or_else(|| "Not a valid identifier")?.kind;
-    if !lexer.next().is_none() {
+    let new_token = lexer.next().ok_or("Not a valid identifier")?.kind;
+    if lexer.next().is_some() {
         return Err("Not a valid identifier".to_owned());
     }
 
@@ -95,7 +95,7 @@ pub(crate) fn rename(
             ranges.into_iter().for_each(|range| {
                 let text_edit = TextEdit {
                     delete: range,
-                    insert: SmolStr::new(&new_name),
+                    insert: SmolStr::new(new_name),
                 };
                 edits
                     .entry(file_id)
@@ -120,9 +120,9 @@ fn find_def(
     let tok = best_token_at_offset(parse.syntax(), pos)?;
     // let source_map = db.souce_map(fpos.file_id);
 
-    let def = semantics::classify_node(&sema, &tok.parent()?)?;
+    let def = semantics::classify_node(sema, &tok.parent()?)?;
 
-    if SmolStr::from(tok.text()) != SmolStr::from(def.name(sema.db.upcast())?) {
+    if tok.text() != def.name(sema.db.upcast())? {
         return Some(Either::Right(String::from("Can't rename aliased names")));
     }
     Some(Either::Left((tok.text_range(), def)))
@@ -0,0 +1,92 @@
+use syntax::{TextRange, TextSize};
+
+use crate::{DefDatabase, FileId};
+
+/// For every position, the ranges an expanding selection goes through, innermost first: the name
+/// under the cursor, then the expressions, statements and items around it up to the whole file.
+pub(crate) fn selection_ranges(
+    db: &dyn DefDatabase,
+    file_id: FileId,
+    positions: Vec<TextSize>,
+) -> Vec<Vec<TextRange>> {
+    let root = db.parse(file_id).syntax_node();
+    positions
+        .into_iter()
+        .map(|pos| {
+            let mut ranges = Vec::new();
+            let node = match root.token_at_offset(pos).right_biased() {
+                // Whitespace and comments don't select as themselves.
+                Some(token) if token.kind().is_whitespace() => token.parent(),
+                Some(token) => {
+                    ranges.push(token.text_range());
+                    token.parent()
+                }
+                None => Some(root.clone()),
+            };
+            for node in node.into_iter().flat_map(|node| node.ancestors()) {
+                let range = node.text_range();
+                if ranges.last() != Some(&range) {
+                    ranges.push(range);
+                }
+            }
+            ranges
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::base::SourceDatabase;
+    use crate::tests::TestDB;
+
+    #[track_caller]
+    fn check(fixture: &str, expect: Expect) {
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        let src = db.file_content(f[0].file_id);
+        let mut got = String::new();
+        for ranges in super::selection_ranges(&db, f[0].file_id, vec![f[0].pos]) {
+            for range in ranges {
+                got += &format!("{range:?} {}\n", src[range].replace('\n', "⏎"));
+            }
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[test]
+    fn grows_outwards() {
+        check(
+            r#"
+fn main(x) {
+  let y = add(x, o$0ne)
+  y
+}
+"#,
+            expect![[r#"
+                30..33 one
+                26..34 (x, one)
+                23..34 add(x, one)
+                15..34 let y = add(x, one)
+                11..40 {⏎  let y = add(x, one)⏎  y⏎}
+                0..40 fn main(x) {⏎  let y = add(x, one)⏎  y⏎}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn whitespace() {
+        check(
+            r#"
+fn main() {
+  [1, $0 2]
+}
+"#,
+            expect![[r#"
+                14..21 [1,  2]
+                10..23 {⏎  [1,  2]⏎}
+                0..23 fn main() {⏎  [1,  2]⏎}
+            "#]],
+        );
+    }
+}
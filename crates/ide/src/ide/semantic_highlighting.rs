@@ -39,7 +39,7 @@ pub(crate) fn highlight(
 
                     if let Definition::Local(local) = def {
                         let ty = local.ty(db);
-                        if let Ty::Function {..} = ty {
+                        if let Ty::Function {..} = ty.unalias() {
                             return Some(HlTag::Function);
                         }
                     };
@@ -49,14 +49,14 @@ pub(crate) fn highlight(
                     }
                 },
                 ast::Name(node) => {
-                    if let Some(_) = ast::Variant::cast(node.syntax().parent()?) {
+                    if ast::Variant::cast(node.syntax().parent()?).is_some() {
                         return Some(HlTag::Constructor)
                     }
                 },
                 _ => return None,
             }
         };
-        return None;
+        None
     };
 
     let (first_tok, end_pos) = match range {
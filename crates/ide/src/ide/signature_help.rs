@@ -1,11 +1,10 @@
 use itertools::Itertools;
 use syntax::{ast, match_ast, rowan::Direction, NodeOrToken, SyntaxToken, TextRange, TextSize, T};
 
-
 use crate::{
     def::Semantics,
     ty::{self, display::TyDisplay, TyDatabase},
-    DefDatabase, FilePos,
+    FilePos,
 };
 use syntax::ast::AstNode;
 
@@ -112,7 +111,7 @@ fn signature_help_for_call(
 
     let resolved = sema.analyze(&token.parent()?)?.type_of_expr(&expr)?;
 
-    let ty::Ty::Function { params, return_ } = resolved else {
+    let ty::Ty::Function { params, return_ } = resolved.unalias() else {
         return None;
     };
 
@@ -146,9 +145,9 @@ fn signature_help_for_call(
         };
         if is_pipe && idx == 0 {
             res.signature.push_str("|> ");
-            res.push_param("|> ", &parameter.as_str());
+            res.push_param("|> ", parameter.as_str());
         } else {
-            res.push_param("(", &parameter.as_str());
+            res.push_param("(", parameter.as_str());
         }
 
         if is_use && idx == params2_mut.len() - 1 {
@@ -183,7 +182,7 @@ fn move_element<T>(vec: &mut Vec<T>, from_index: usize, to_index: usize) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     use crate::tests::TestDB;
     use expect_test::{expect, Expect};
 
@@ -254,4 +253,14 @@ mod tests {
             expect![r#"(label2: String, <label1: Int>) -> String"#],
         )
     }
+
+    #[test]
+    fn no_signature_outside_call() {
+        check_no(
+            r#"
+        fn main() {
+            1$0
+        }"#,
+        )
+    }
 }
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+use syntax::ast::AstNode;
+use syntax::{SyntaxNode, TextRange};
+
+use crate::def::{FileSymbol, SymbolKind};
+use crate::{DefDatabase, FileId};
+
+use super::NavigationTarget;
+
+/// Workspace symbol search stops after this many matches.
+const MAX_WORKSPACE_SYMBOLS: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSymbol {
+    pub name: SmolStr,
+    pub kind: SymbolKind,
+    pub full_range: TextRange,
+    pub focus_range: TextRange,
+    pub is_deprecated: bool,
+    pub children: Vec<DocumentSymbol>,
+}
+
+impl DocumentSymbol {
+    fn new(
+        name: SmolStr,
+        kind: SymbolKind,
+        node: &SyntaxNode,
+        name_node: Option<SyntaxNode>,
+    ) -> Self {
+        let full_range = node.text_range();
+        Self {
+            name,
+            kind,
+            full_range,
+            focus_range: name_node.map_or(full_range, |n| n.text_range()),
+            is_deprecated: false,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl From<&FileSymbol> for DocumentSymbol {
+    fn from(symbol: &FileSymbol) -> Self {
+        Self {
+            name: symbol.name.clone(),
+            kind: symbol.kind,
+            full_range: symbol.full_range,
+            focus_range: symbol.focus_range,
+            is_deprecated: symbol.is_deprecated,
+            children: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceSymbol {
+    pub name: SmolStr,
+    pub kind: SymbolKind,
+    /// The module the symbol is defined in, followed by the type for variants.
+    pub container: SmolStr,
+    pub nav: NavigationTarget,
+    pub is_deprecated: bool,
+}
+
+/// The outline of a module, in source order: its definitions as indexed for workspace symbols,
+/// along with imports and the labelled fields of variants.
+pub(crate) fn document_symbols(db: &dyn DefDatabase, file_id: FileId) -> Vec<DocumentSymbol> {
+    let root = db.parse(file_id).syntax_node();
+    let items = db.module_items(file_id);
+    let mut symbols = Vec::new();
+
+    for (_, import) in items.module_imports() {
+        let node = import.ast_ptr.to_node(&root);
+        let path = node.module_path().map(|p| p.syntax().clone());
+        symbols.push(DocumentSymbol::new(
+            import.name.clone(),
+            SymbolKind::Module,
+            node.syntax(),
+            path,
+        ));
+    }
+
+    // Positional fields have nothing to show.
+    let mut fields: HashMap<TextRange, Vec<DocumentSymbol>> = HashMap::new();
+    for (_, adt) in items.adts() {
+        for variant in adt.variants.clone() {
+            let variant = &items[variant];
+            let range = variant.ast_ptr.to_node(&root).syntax().text_range();
+            for field in variant.fields.clone() {
+                let field = &items[field];
+                let Some(label) = &field.label else {
+                    continue;
+                };
+                let node = field.ast_ptr.to_node(&root);
+                let name = node.label().map(|n| n.syntax().clone());
+                fields.entry(range).or_default().push(DocumentSymbol::new(
+                    label.clone(),
+                    SymbolKind::Field,
+                    node.syntax(),
+                    name,
+                ));
+            }
+        }
+    }
+
+    for symbol in db.module_symbols(file_id).iter() {
+        let mut document_symbol = DocumentSymbol::from(symbol);
+        if symbol.container.is_none() {
+            symbols.push(document_symbol);
+            continue;
+        }
+        // Variants directly follow their type.
+        document_symbol.children = fields.remove(&symbol.full_range).unwrap_or_default();
+        if let Some(adt) = symbols.last_mut() {
+            adt.children.push(document_symbol);
+        }
+    }
+
+    symbols.sort_by_key(|symbol| symbol.full_range.start());
+    symbols
+}
+
+/// Definitions across the package graph whose name fuzzily matches `query`, best matches first.
+pub(crate) fn workspace_symbols(
+    db: &dyn DefDatabase,
+    query: &str,
+    include_dependencies: bool,
+) -> Vec<WorkspaceSymbol> {
+    let query = query.to_lowercase();
+    let graph = db.package_graph();
+    let mut found = Vec::new();
+    for package in graph.iter() {
+        let info = &graph[package];
+        if !info.is_local && !include_dependencies {
+            continue;
+        }
+        let source_root_id = db.file_source_root(info.gleam_toml);
+        let module_map = db.module_map(source_root_id);
+        for (file_id, _) in db.source_root(source_root_id).module_files() {
+            let Some(module) = module_map.module_name_for_file(file_id) else {
+                continue;
+            };
+            for symbol in db.module_symbols(file_id).iter() {
+                let Some(score) = fuzzy_score(&query, &symbol.name) else {
+                    continue;
+                };
+                let container = match &symbol.container {
+                    Some(adt) => format!("{module}.{adt}").into(),
+                    None => module.clone(),
+                };
+                found.push((
+                    score,
+                    WorkspaceSymbol {
+                        name: symbol.name.clone(),
+                        kind: symbol.kind,
+                        container,
+                        nav: NavigationTarget {
+                            file_id,
+                            full_range: symbol.full_range,
+                            focus_range: symbol.focus_range,
+                        },
+                        is_deprecated: symbol.is_deprecated,
+                    },
+                ));
+            }
+        }
+    }
+
+    found.sort_by(|(a_score, a), (b_score, b)| {
+        (a_score, &a.name, &a.container).cmp(&(b_score, &b.name, &b.container))
+    });
+    found
+        .into_iter()
+        .take(MAX_WORKSPACE_SYMBOLS)
+        .map(|(_, symbol)| symbol)
+        .collect()
+}
+
+/// Scores `name` against an already lowercased `query`, lower is better. The query has to be a
+/// subsequence of the name; exact matches beat prefixes, which beat matches with fewer gaps.
+fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    let name = name.to_lowercase();
+    if name == query {
+        return Some(0);
+    }
+    if name.starts_with(query) {
+        return Some(1);
+    }
+    let mut gaps = 0;
+    let mut chars = name.chars();
+    for wanted in query.chars() {
+        loop {
+            match chars.next() {
+                Some(c) if c == wanted => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+    Some(2 + gaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::TestDB;
+
+    use super::DocumentSymbol;
+
+    #[track_caller]
+    fn check_document(fixture: &str, expect: Expect) {
+        fn render(got: &mut String, symbol: &DocumentSymbol, depth: usize) {
+            *got += &format!(
+                "{}{:?} {} {:?} {:?}{}\n",
+                "  ".repeat(depth),
+                symbol.kind,
+                symbol.name,
+                symbol.full_range,
+                symbol.focus_range,
+                if symbol.is_deprecated {
+                    " deprecated"
+                } else {
+                    ""
+                },
+            );
+            for child in &symbol.children {
+                render(got, child, depth + 1);
+            }
+        }
+
+        let (db, f) = TestDB::from_fixture(fixture).unwrap();
+        let mut got = String::new();
+        for symbol in super::document_symbols(&db, f.files()[0]) {
+            render(&mut got, &symbol, 0);
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[track_caller]
+    fn check_workspace(fixture: &str, query: &str, include_dependencies: bool, expect: Expect) {
+        let (db, _) = TestDB::from_fixture(fixture).unwrap();
+        let mut got = String::new();
+        for symbol in super::workspace_symbols(&db, query, include_dependencies) {
+            got += &format!(
+                "{:?} {} in {} {:?} {:?}\n",
+                symbol.kind,
+                symbol.name,
+                symbol.container,
+                symbol.nav.file_id,
+                symbol.nav.focus_range,
+            );
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[test]
+    fn document_outline() {
+        check_document(
+            r#"
+#- main.gleam
+import gleam/list
+pub type Shape {
+  Circle(radius: Float)
+  Rect(Float, height: Float)
+}
+type Name = String
+@deprecated("Use `area` instead")
+pub fn size(s: Shape) { 1.0 }
+const pi = 3.14
+"#,
+            expect![[r#"
+                Module gleam/list 0..17 7..17
+                Adt Shape 18..89 27..32
+                  Variant Circle 37..58 37..43
+                    Field radius 44..57 44..50
+                  Variant Rect 61..87 61..65
+                    Field height 73..86 73..79
+                TypeAlias Name 90..108 95..99
+                Function size 109..172 150..154 deprecated
+                Constant pi 173..188 179..181
+            "#]],
+        );
+    }
+
+    #[test]
+    fn fuzzy_search() {
+        let fixture = r#"
+#- lib.gleam package:dep
+pub fn map_values(x) { x }
+#- shapes.gleam
+pub type Shape {
+  Circle(Float)
+}
+#- main.gleam
+pub fn map() { 1 }
+fn make_adder() { 1 }
+const max = 1
+"#;
+        check_workspace(
+            fixture,
+            "ma",
+            false,
+            expect![[r#"
+            Function make_adder in main FileId(2) 22..32
+            Function map in main FileId(2) 7..10
+            Constant max in main FileId(2) 47..50
+        "#]],
+        );
+        check_workspace(
+            fixture,
+            "map",
+            true,
+            expect![[r#"
+            Function map in main FileId(2) 7..10
+            Function map_values in lib FileId(0) 7..17
+        "#]],
+        );
+        check_workspace(
+            fixture,
+            "circ",
+            false,
+            expect![[r#"
+            Variant Circle in shapes.Shape FileId(1) 19..25
+        "#]],
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use syntax::ast::{self, AstNode};
+
+use crate::{DefDatabase, FileRange};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Todo {
+    pub frange: FileRange,
+    pub message: Option<String>,
+}
+
+/// Every `todo` in the local packages, dependencies are not our business.
+pub(crate) fn todos(db: &dyn DefDatabase) -> Vec<Todo> {
+    let graph = db.package_graph();
+    let mut todos = Vec::new();
+    for package in graph.iter() {
+        let info = &graph[package];
+        if !info.is_local {
+            continue;
+        }
+        let source_root = db.source_root(db.file_source_root(info.gleam_toml));
+        for (file_id, _) in source_root.module_files() {
+            let root = db.parse(file_id).syntax_node();
+            todos.extend(root.descendants().filter_map(ast::Todo::cast).map(|todo| {
+                let message = todo.message().map(|msg| match &msg {
+                    ast::Expr::Literal(lit) => {
+                        lit.syntax().text().to_string().trim_matches('"').into()
+                    }
+                    _ => msg.syntax().text().to_string(),
+                });
+                Todo {
+                    frange: FileRange::new(file_id, todo.syntax().text_range()),
+                    message,
+                }
+            }));
+        }
+    }
+    todos.sort_by_key(|todo| (todo.frange.file_id, todo.frange.range.start()));
+    todos
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::tests::TestDB;
+
+    #[track_caller]
+    fn check(fixture: &str, expect: Expect) {
+        let (db, _) = TestDB::from_fixture(fixture).unwrap();
+        let mut got = String::new();
+        for todo in super::todos(&db) {
+            got += &format!(
+                "{:?} {:?}: {:?}\n",
+                todo.frange.file_id, todo.frange.range, todo.message
+            );
+        }
+        expect.assert_eq(&got);
+    }
+
+    #[test]
+    fn workspace_todos() {
+        check(
+            r#"
+#- a.gleam
+pub fn a() { todo }
+#- b.gleam
+fn b(x) {
+    case x {
+        1 -> todo as "handle one"
+        _ -> panic
+    }
+}
+"#,
+            expect![[r#"
+                FileId(0) 13..17: None
+                FileId(1) 36..56: Some("handle one")
+            "#]],
+        );
+    }
+}
@@ -11,15 +11,17 @@ mod tests;
 pub const DEFAULT_IMPORT_FILE: &str = "gleam.toml";
 
 pub use self::ide::{
-    Analysis, AnalysisHost, Cancelled, CompletionItem, CompletionItemKind, CompletionRelevance,
-    GotoDefinitionResult, HlRange, HlRelated, HlTag, HoverResult, SignatureHelp,
+    Analysis, AnalysisHost, Assist, AssistKind, CallHierarchyItem, Cancelled, CompletionItem,
+    CompletionItemKind, CompletionRelevance, DocumentSymbol, Fold, FoldKind, GotoDefinitionResult,
+    GotoTypeDefinitionResult, HlRange, HlRelated, HlTag, HoverResult, IncomingCall, InlayHint,
+    InlayHintKind, InlayHintsConfig, OutgoingCall, SignatureHelp, Todo, WorkspaceSymbol,
 };
 pub use base::{
     module_name, Change, Dependency, FileId, FilePos, FileRange, FileSet, InFile, ModuleMap,
     PackageGraph, PackageId, PackageInfo, PackageRoot, SourceDatabase, SourceRoot, SourceRootId,
     Target, VfsPath,
 };
-pub use def::DefDatabase;
+pub use def::{DefDatabase, SymbolKind};
 pub use diagnostic::{Diagnostic, DiagnosticKind, Severity};
 pub use text_edit::{TextEdit, WorkspaceEdit};
 // pub use ty::{InferenceResult, TyDatabase};
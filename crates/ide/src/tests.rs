@@ -3,8 +3,8 @@ use crate::def::{DefDatabaseStorage, InternDatabaseStorage};
 use crate::ide::Upcast;
 use crate::ty::TyDatabaseStorage;
 use crate::{
-    Change, DefDatabase, FileId, FilePos, FileRange, FileSet, PackageGraph, PackageInfo,
-    SourceRoot, VfsPath,
+    Change, DefDatabase, Dependency, FileId, FilePos, FileRange, FileSet, PackageGraph,
+    PackageInfo, SourceRoot, VfsPath,
 };
 use anyhow::{bail, ensure, Context, Result};
 use indexmap::IndexMap;
@@ -47,20 +47,30 @@ impl TestDB {
         let f = Fixture::new(fixture)?;
         let mut db = Self::default();
         let mut change = Change::default();
-        let mut file_set = FileSet::default();
-        for (i, (path, text)) in (0u32..).zip(&f.files) {
+        let mut file_sets = vec![FileSet::default(); f.dependencies.len() + 1];
+        for (i, ((path, text), package)) in (0u32..).zip(f.files.iter().zip(&f.file_packages)) {
             let file = FileId(i);
-            file_set.insert(file, path.clone());
+            let root = package.map_or(0, |idx| idx + 1);
+            file_sets[root].insert(file, path.clone());
 
             change.change_file(file, text.to_owned().into());
         }
-        change.set_roots(vec![SourceRoot::new(file_set, "/".into())]);
+        let mut file_sets = file_sets.into_iter();
+        let mut roots = vec![SourceRoot::new(file_sets.next().unwrap(), "/".into())];
+        for ((name, _), file_set) in f.dependencies.iter().zip(file_sets) {
+            roots.push(SourceRoot::new(file_set, format!("/{name}").into()));
+        }
+        change.set_roots(roots);
         let mut package_graph = PackageGraph::default();
-        package_graph.add_package(
+        let test_package = package_graph.add_package(
             SmolStr::from("test"),
             FileId(f.files.len() as u32 - 1),
             true,
         );
+        for (name, gleam_toml) in &f.dependencies {
+            let package = package_graph.add_package(name.clone(), *gleam_toml, false);
+            package_graph.add_dep(test_package, Dependency { package });
+        }
 
         change.set_package_graph(package_graph);
         change.apply(&mut db);
@@ -72,6 +82,10 @@ impl TestDB {
 pub struct Fixture {
     files: IndexMap<VfsPath, String>,
     file_ids: Vec<FileId>,
+    /// The index into `dependencies` of every file which isn't part of the test package.
+    file_packages: Vec<Option<usize>>,
+    /// Packages the test package depends on, `#- lib.gleam package:name` puts a file into one.
+    dependencies: IndexMap<SmolStr, FileId>,
     markers: Vec<FilePos>,
     package_info: Option<PackageInfo>,
 }
@@ -110,12 +124,17 @@ impl Fixture {
                 ensure!(!missing_header, "Missing path header at the first line");
 
                 let mut iter = header.split(' ');
-                let test_dir = VfsPath::new("/test");
+                let mut test_dir = VfsPath::new("/test");
                 let path = iter.next().context("Missing path")?;
-                let path = test_dir.join(path).unwrap();
+                let mut package = None;
 
                 for prop in iter {
-                    if let Some((_name, target)) = prop
+                    if let Some(name) = prop.strip_prefix("package:") {
+                        test_dir = VfsPath::new(format!("/{name}/src"));
+                        // The gleam.toml is added once every module is known.
+                        this.dependencies.entry(name.into()).or_insert(FileId(0));
+                        package = this.dependencies.get_index_of(name);
+                    } else if let Some((_name, target)) = prop
                         .strip_prefix("input:")
                         .and_then(|input| input.split_once('='))
                     {
@@ -125,14 +144,15 @@ impl Fixture {
                     }
                 }
 
-                if let Some(prev_path) = cur_path.replace(path) {
-                    this.insert_file(prev_path, mem::take(&mut cur_text))?;
+                let path = test_dir.join(path).unwrap();
+                if let Some((prev_path, prev_package)) = cur_path.replace((path, package)) {
+                    this.insert_file(prev_path, prev_package, mem::take(&mut cur_text))?;
                     cur_file.0 += 1;
                 }
             } else {
                 if cur_path.is_none() {
                     missing_header = true;
-                    cur_path = Some(VfsPath::new("/test/test.gleam".to_string()));
+                    cur_path = Some((VfsPath::new("/test/test.gleam"), None));
                 }
 
                 let mut iter = line.chars().peekable();
@@ -155,10 +175,21 @@ impl Fixture {
                 cur_text += "\n";
             }
         }
-        this.insert_file(cur_path.context("Empty fixture")?, cur_text)?;
-
+        let (cur_path, cur_package) = cur_path.context("Empty fixture")?;
+        this.insert_file(cur_path, cur_package, cur_text)?;
+
+        for idx in 0..this.dependencies.len() {
+            let name = this.dependencies.get_index(idx).unwrap().0.clone();
+            let gleam_toml = FileId(this.files.len() as u32);
+            this.insert_file(
+                VfsPath::new(format!("/{name}/gleam.toml")),
+                Some(idx),
+                String::new(),
+            )?;
+            this.dependencies[idx] = gleam_toml;
+        }
         let _: std::result::Result<(), anyhow::Error> =
-            this.insert_file(VfsPath::new("/gleam.toml"), "".to_string());
+            this.insert_file(VfsPath::new("/gleam.toml"), None, "".to_string());
         this.package_info.get_or_insert_with(|| PackageInfo {
             gleam_toml: FileId(this.files.len() as u32 - 1),
             dependencies: Default::default(),
@@ -181,7 +212,12 @@ impl Fixture {
         Ok(this)
     }
 
-    fn insert_file(&mut self, path: VfsPath, mut text: String) -> Result<()> {
+    fn insert_file(
+        &mut self,
+        path: VfsPath,
+        package: Option<usize>,
+        mut text: String,
+    ) -> Result<()> {
         let file = FileId(self.files.len() as u32);
         text.truncate(text.trim_end().len());
         ensure!(
@@ -190,6 +226,7 @@ impl Fixture {
             path
         );
         self.file_ids.push(file);
+        self.file_packages.push(package);
         Ok(())
     }
 
@@ -2,10 +2,14 @@ use std::fmt;
 
 use smol_str::SmolStr;
 
-use crate::def::{hir::Adt, hir_def::ModuleDefId};
+use crate::def::{
+    hir::{Adt, TypeAlias},
+    hir_def::ModuleDefId,
+};
 
 use super::{Ty, TyDatabase};
 
+#[allow(dead_code)]
 pub trait TyWrite: fmt::Write {
     fn start_location_link(&mut self, location: ModuleDefId);
     fn end_location_link(&mut self);
@@ -27,6 +31,7 @@ pub struct TyFormatter<'a> {
     pub db: &'a dyn TyDatabase,
     fmt: &'a mut dyn TyWrite,
     buf: String,
+    keep_aliases: bool,
 }
 
 pub trait TyDisplay {
@@ -38,7 +43,11 @@ pub trait TyDisplay {
     where
         Self: Sized,
     {
-        TyDisplayWrapper { db, t: self }
+        TyDisplayWrapper {
+            db,
+            t: self,
+            keep_aliases: false,
+        }
     }
 }
 
@@ -84,14 +93,22 @@ impl From<fmt::Error> for TyDisplayError {
 pub struct TyDisplayWrapper<'a, T> {
     db: &'a dyn TyDatabase,
     t: &'a T,
+    keep_aliases: bool,
 }
 
 impl<T: TyDisplay> TyDisplayWrapper<'_, T> {
+    /// Show types written through an alias by the alias' name instead of what it expands to.
+    pub fn keep_aliases(mut self) -> Self {
+        self.keep_aliases = true;
+        self
+    }
+
     pub fn write_to<F: TyWrite>(&self, f: &mut F) -> Result<(), TyDisplayError> {
         self.t.ty_fmt(&mut TyFormatter {
             db: self.db,
             fmt: f,
             buf: String::with_capacity(20),
+            keep_aliases: self.keep_aliases,
         })
     }
 }
@@ -121,6 +138,7 @@ impl TyDisplay for Ty {
             Ty::Float => write!(f, "Float"),
             Ty::String => write!(f, "String"),
             Ty::BitArray => write!(f, "BitArray"),
+            Ty::UtfCodepoint => write!(f, "UtfCodepoint"),
             Ty::Result { ok, err } => {
                 write!(f, "Result(")?;
                 ok.ty_fmt(f)?;
@@ -158,7 +176,7 @@ impl TyDisplay for Ty {
             }
             Ty::Tuple { fields } => {
                 write!(f, "#(")?;
-                f.write_joined(fields.as_ref().clone().into_iter(), ", ")?;
+                f.write_joined(fields.as_ref().clone(), ", ")?;
                 write!(f, ")")
             }
             Ty::Adt { adt_id, params } => {
@@ -174,15 +192,32 @@ impl TyDisplay for Ty {
                 // }
                 let adt = Adt { id: *adt_id };
                 let name = adt.name(db.upcast());
-                if params.len() > 0 {
+                if !params.is_empty() {
                     write!(f, "{}", name)?;
                     write!(f, "(")?;
-                    f.write_joined(params.as_ref().clone().into_iter(), ", ")?;
+                    f.write_joined(params.as_ref().clone(), ", ")?;
                     write!(f, ")")
                 } else {
                     write!(f, "{}", name)
                 }
             }
+            Ty::Alias {
+                alias_id,
+                params,
+                ty,
+            } => {
+                if !f.keep_aliases {
+                    return ty.ty_fmt(f);
+                }
+                let name = TypeAlias { id: *alias_id }.name(db.upcast());
+                write!(f, "{}", name)?;
+                if !params.is_empty() {
+                    write!(f, "(")?;
+                    f.write_joined(params.as_ref().clone(), ", ")?;
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -0,0 +1,932 @@
+//! Exhaustiveness and usefulness checking of patterns.
+//!
+//! This is the pattern matrix algorithm from "Warnings for pattern matching"
+//! (Maranget, 2007), extended to produce example values which are not covered.
+//! Patterns are first lowered into constructors applied to sub-patterns, so
+//! the algorithm itself doesn't need to know about Gleam's syntax.
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+use syntax::ast::AstNode;
+
+use crate::def::{
+    body::{Body, BodySourceMap},
+    hir::{Adt, Variant},
+    hir_def::{AdtId, FunctionId},
+    module::{Clause, Expr, ExprId, Pattern, PatternId, Statement},
+};
+
+use super::{InferenceResult, Ty, TyDatabase};
+
+/// How many missing patterns are reported. The search stops at one more than this, so a
+/// longer list means there are more.
+pub(crate) const MAX_MISSING: usize = 8;
+
+/// Problems found while checking the patterns of a function body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternDiagnostic {
+    /// A case expression doesn't cover every possible value of its subjects.
+    InexhaustiveCase { case: ExprId, missing: Vec<String> },
+    /// A `let` pattern can fail to match, but it isn't a `let assert`.
+    InexhaustiveLet {
+        pattern: PatternId,
+        missing: Vec<String>,
+    },
+    /// A case clause only matches values that earlier clauses already match.
+    UnreachableClause { case: ExprId, clause: usize },
+}
+
+/// An example of a value which a case doesn't match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MissingPattern {
+    /// The patterns for each subject, separated by commas.
+    pub(crate) text: String,
+    /// The variant the example is made with, if the case has a single subject.
+    pub(crate) variant: Option<Variant>,
+}
+
+pub(crate) fn check_patterns(db: &dyn TyDatabase, fn_id: FunctionId) -> Vec<PatternDiagnostic> {
+    with_checker(db, fn_id, check_body)
+}
+
+/// The values the clauses of `case` don't match, unless they can't be checked.
+pub(crate) fn missing_patterns(
+    db: &dyn TyDatabase,
+    fn_id: FunctionId,
+    case: ExprId,
+) -> Option<Vec<MissingPattern>> {
+    with_checker(db, fn_id, |checker| {
+        let Expr::Case { subjects, clauses } = &checker.body[case] else {
+            return None;
+        };
+        if subjects.is_empty() || clauses.is_empty() {
+            return None;
+        }
+        let matrix = checker.case_matrix(case, subjects.len(), clauses, &mut Vec::new());
+        if checker.has_unresolved {
+            return None;
+        }
+        let missing = checker.missing_rows(subjects.len(), &matrix);
+        Some(
+            missing
+                .iter()
+                .map(|row| MissingPattern {
+                    text: checker.display_row(row),
+                    variant: match &row[..] {
+                        [Pat::Ctor(Ctor::Variant(variant), _)] => Some(*variant),
+                        _ => None,
+                    },
+                })
+                .collect(),
+        )
+    })
+}
+
+fn with_checker<R>(
+    db: &dyn TyDatabase,
+    fn_id: FunctionId,
+    f: impl FnOnce(&mut Checker<'_>) -> R,
+) -> R {
+    let (body, source_map) = db.body_with_source_map(fn_id);
+    let infer = db.infer_function(fn_id);
+    let mut checker = Checker {
+        db,
+        body: &body,
+        source_map: &source_map,
+        root: db
+            .parse(db.lookup_intern_function(fn_id).file_id)
+            .syntax_node(),
+        infer: &infer,
+        qualifiers: HashMap::new(),
+        next_opaque: 0,
+        has_unresolved: false,
+    };
+    f(&mut checker)
+}
+
+fn check_body(checker: &mut Checker<'_>) -> Vec<PatternDiagnostic> {
+    let body = checker.body;
+    let mut diagnostics = Vec::new();
+    for (expr_id, expr) in body.exprs() {
+        match expr {
+            Expr::Case { subjects, clauses } => {
+                checker.check_case(expr_id, subjects.len(), clauses, &mut diagnostics);
+            }
+            Expr::Block { stmts } => {
+                for stmt in stmts {
+                    if let Statement::Let {
+                        pattern,
+                        assert: false,
+                        ..
+                    } = stmt
+                    {
+                        checker.has_unresolved = false;
+                        let row = vec![checker.lower(*pattern)];
+                        let missing = checker.missing(1, &[row]);
+                        if !missing.is_empty() && !checker.has_unresolved {
+                            diagnostics.push(PatternDiagnostic::InexhaustiveLet {
+                                pattern: *pattern,
+                                missing,
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    diagnostics
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Ctor {
+    Bool(bool),
+    Nil,
+    Ok,
+    Error,
+    Variant(Variant),
+    Tuple(usize),
+    EmptyList,
+    Cons,
+    /// Literals are compared by their source text.
+    Literal(SmolStr),
+    /// Patterns we can't reason about, like string prefixes and bit arrays.
+    /// They are never equal to any other constructor.
+    Opaque(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Pat {
+    Wild,
+    Ctor(Ctor, Vec<Pat>),
+    Or(Vec<Pat>),
+}
+
+type Row = Vec<Pat>;
+
+struct Checker<'a> {
+    db: &'a dyn TyDatabase,
+    body: &'a Body,
+    source_map: &'a BodySourceMap,
+    root: syntax::SyntaxNode,
+    infer: &'a InferenceResult,
+    /// Module accessors used for constructors of a type, to print examples the
+    /// way the user refers to them.
+    qualifiers: HashMap<AdtId, SmolStr>,
+    next_opaque: u32,
+    /// Set when a constructor couldn't be resolved or a row of a clause has the wrong
+    /// number of patterns. That is already an error, so missing patterns aren't
+    /// reported on top of it.
+    has_unresolved: bool,
+}
+
+impl Checker<'_> {
+    fn check_case(
+        &mut self,
+        case: ExprId,
+        width: usize,
+        clauses: &[Clause],
+        diagnostics: &mut Vec<PatternDiagnostic>,
+    ) {
+        if width == 0 || clauses.is_empty() {
+            return;
+        }
+        let matrix = self.case_matrix(case, width, clauses, diagnostics);
+        if self.has_unresolved {
+            return;
+        }
+        let missing = self.missing(width, &matrix);
+        if !missing.is_empty() {
+            diagnostics.push(PatternDiagnostic::InexhaustiveCase { case, missing });
+        }
+    }
+
+    /// The rows of the clauses which cover values for later ones, reporting clauses which are
+    /// unreachable along the way.
+    fn case_matrix(
+        &mut self,
+        case: ExprId,
+        width: usize,
+        clauses: &[Clause],
+        diagnostics: &mut Vec<PatternDiagnostic>,
+    ) -> Vec<Row> {
+        self.has_unresolved = false;
+        let mut matrix: Vec<Row> = Vec::new();
+        for (idx, clause) in clauses.iter().enumerate() {
+            let mut rows = Vec::new();
+            for patterns in clause.rows() {
+                // Rows with the wrong number of patterns can't be checked, so the case as a
+                // whole isn't reported either.
+                if patterns.len() != width {
+                    self.has_unresolved = true;
+                    continue;
+                }
+                let row: Row = patterns.iter().map(|pat| self.lower(*pat)).collect();
+                rows.push(row);
+            }
+            if rows.is_empty() {
+                continue;
+            }
+            if !rows.iter().any(|row| self.is_useful(&matrix, row)) {
+                diagnostics.push(PatternDiagnostic::UnreachableClause { case, clause: idx });
+            }
+            // A guard may fail, so the clause doesn't cover anything for later clauses.
+            if clause.guard.is_none() {
+                matrix.extend(rows);
+            }
+        }
+        matrix
+    }
+
+    /// Examples of values which none of the rows match.
+    fn missing(&self, width: usize, matrix: &[Row]) -> Vec<String> {
+        self.missing_rows(width, matrix)
+            .iter()
+            .map(|row| self.display_row(row))
+            .collect()
+    }
+
+    /// Values which none of the rows match, without duplicates.
+    fn missing_rows(&self, width: usize, matrix: &[Row]) -> Vec<Row> {
+        let mut rows: Vec<Row> = Vec::new();
+        for row in self.witnesses(matrix, &vec![Pat::Wild; width]) {
+            if !rows.contains(&row) {
+                rows.push(row);
+            }
+        }
+        rows
+    }
+
+    fn display_row(&self, row: &[Pat]) -> String {
+        row.iter()
+            .map(|pat| self.display(pat))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn lower(&mut self, pattern: PatternId) -> Pat {
+        match &self.body[pattern] {
+            Pattern::Missing
+            | Pattern::Hole
+            | Pattern::Variable { .. }
+            | Pattern::Spread { .. } => Pat::Wild,
+            Pattern::AsPattern { pattern, .. } => self.lower(*pattern),
+            Pattern::AlternativePattern { patterns } => {
+                Pat::Or(patterns.iter().map(|pat| self.lower(*pat)).collect())
+            }
+            Pattern::Literal { .. } => match self.source_text(pattern) {
+                Some(text) => Pat::Ctor(Ctor::Literal(text), Vec::new()),
+                None => self.opaque(),
+            },
+            Pattern::Concat { .. } | Pattern::BitArray { .. } => self.opaque(),
+            Pattern::Tuple { fields } => {
+                let fields = fields
+                    .iter()
+                    .map(|pat| self.lower(*pat))
+                    .collect::<Vec<_>>();
+                Pat::Ctor(Ctor::Tuple(fields.len()), fields)
+            }
+            Pattern::List { elements } => {
+                let (elements, mut list) = match elements.split_last() {
+                    Some((last, rest)) if matches!(self.body[*last], Pattern::Spread { .. }) => {
+                        (rest, Pat::Wild)
+                    }
+                    _ => (&elements[..], Pat::Ctor(Ctor::EmptyList, Vec::new())),
+                };
+                for elem in elements.iter().rev() {
+                    list = Pat::Ctor(Ctor::Cons, vec![self.lower(*elem), list]);
+                }
+                list
+            }
+            Pattern::VariantRef {
+                name,
+                module,
+                fields,
+            } => {
+                let Some((ctor, labels)) = self.resolve_ctor(pattern, name) else {
+                    self.has_unresolved = true;
+                    return self.opaque();
+                };
+                if let (Ctor::Variant(variant), Some(module)) = (&ctor, module) {
+                    self.qualifiers.insert(variant.parent().id, module.clone());
+                }
+                // Labelled fields go to their position, the rest fill the gaps in order.
+                let mut slots: Vec<Option<Pat>> = vec![None; labels.len()];
+                let mut positional = Vec::new();
+                for (label, pat) in fields {
+                    let position = label
+                        .as_ref()
+                        .and_then(|label| labels.iter().position(|l| l.as_ref() == Some(label)));
+                    match position {
+                        Some(position) => slots[position] = Some(self.lower(*pat)),
+                        None => positional.push(self.lower(*pat)),
+                    }
+                }
+                let mut positional = positional.into_iter();
+                let fields = slots
+                    .into_iter()
+                    .map(|slot| slot.or_else(|| positional.next()).unwrap_or(Pat::Wild))
+                    .collect();
+                Pat::Ctor(ctor, fields)
+            }
+        }
+    }
+
+    /// Resolves a constructor pattern, returning the field labels of the constructor.
+    fn resolve_ctor(
+        &self,
+        pattern: PatternId,
+        name: &SmolStr,
+    ) -> Option<(Ctor, Vec<Option<SmolStr>>)> {
+        let ctor = match (self.infer.ty_for_pattern(pattern).unalias(), name.as_str()) {
+            (Ty::Bool, "True") => Ctor::Bool(true),
+            (Ty::Bool, "False") => Ctor::Bool(false),
+            (Ty::Nil, "Nil") => Ctor::Nil,
+            (Ty::Result { .. }, "Ok") => return Some((Ctor::Ok, vec![None])),
+            (Ty::Result { .. }, "Error") => return Some((Ctor::Error, vec![None])),
+            (Ty::Adt { adt_id, .. }, _) => {
+                let variant = Adt { id: *adt_id }
+                    .variants(self.db.upcast())
+                    .into_iter()
+                    .find(|variant| variant.name(self.db.upcast()) == *name)?;
+                let labels = variant
+                    .fields(self.db.upcast())
+                    .into_iter()
+                    .map(|field| field.label(self.db.upcast()))
+                    .collect();
+                return Some((Ctor::Variant(variant), labels));
+            }
+            _ => return None,
+        };
+        Some((ctor, Vec::new()))
+    }
+
+    fn source_text(&self, pattern: PatternId) -> Option<SmolStr> {
+        let src = self.source_map.node_for_pattern(pattern)?;
+        Some(
+            src.value
+                .to_node(&self.root)
+                .syntax()
+                .text()
+                .to_string()
+                .into(),
+        )
+    }
+
+    fn opaque(&mut self) -> Pat {
+        self.next_opaque += 1;
+        Pat::Ctor(Ctor::Opaque(self.next_opaque), Vec::new())
+    }
+
+    fn arity(&self, ctor: &Ctor) -> usize {
+        match ctor {
+            Ctor::Ok | Ctor::Error => 1,
+            Ctor::Cons => 2,
+            Ctor::Tuple(len) => *len,
+            Ctor::Variant(variant) => variant.fields(self.db.upcast()).len(),
+            Ctor::Bool(_) | Ctor::Nil | Ctor::EmptyList | Ctor::Literal(_) | Ctor::Opaque(_) => 0,
+        }
+    }
+
+    /// Every constructor of the type `ctor` belongs to, if there are finitely many.
+    fn all_ctors(&self, ctor: &Ctor) -> Option<Vec<Ctor>> {
+        let all = match ctor {
+            Ctor::Bool(_) => vec![Ctor::Bool(true), Ctor::Bool(false)],
+            Ctor::Nil => vec![Ctor::Nil],
+            Ctor::Ok | Ctor::Error => vec![Ctor::Ok, Ctor::Error],
+            Ctor::Tuple(len) => vec![Ctor::Tuple(*len)],
+            Ctor::EmptyList | Ctor::Cons => vec![Ctor::EmptyList, Ctor::Cons],
+            Ctor::Variant(variant) => variant
+                .parent()
+                .variants(self.db.upcast())
+                .into_iter()
+                .map(Ctor::Variant)
+                .collect(),
+            Ctor::Literal(_) | Ctor::Opaque(_) => return None,
+        };
+        Some(all)
+    }
+
+    /// Whether `row` matches any value which no row in `matrix` matches. This is `witnesses`
+    /// without building the values, so it stops at the first one.
+    fn is_useful(&self, matrix: &[Row], row: &[Pat]) -> bool {
+        let Some((head, rest)) = row.split_first() else {
+            return matrix.is_empty();
+        };
+
+        let matrix = expand_or(matrix);
+        match head {
+            Pat::Or(alternatives) => alternatives.iter().any(|alt| {
+                let row: Row = std::iter::once(alt.clone())
+                    .chain(rest.iter().cloned())
+                    .collect();
+                self.is_useful(&matrix, &row)
+            }),
+            Pat::Ctor(ctor, fields) => {
+                let specialized = self.specialize(&matrix, ctor);
+                let row: Row = fields.iter().chain(rest).cloned().collect();
+                self.is_useful(&specialized, &row)
+            }
+            Pat::Wild => {
+                let present: Vec<&Ctor> = matrix
+                    .iter()
+                    .filter_map(|row| match &row[0] {
+                        Pat::Ctor(ctor, _) => Some(ctor),
+                        _ => None,
+                    })
+                    .collect();
+                let all = present.first().and_then(|ctor| self.all_ctors(ctor));
+                match all {
+                    Some(all) if all.iter().all(|ctor| present.contains(&ctor)) => {
+                        all.iter().any(|ctor| {
+                            let specialized = self.specialize(&matrix, ctor);
+                            let row: Row = std::iter::repeat(Pat::Wild)
+                                .take(self.arity(ctor))
+                                .chain(rest.iter().cloned())
+                                .collect();
+                            self.is_useful(&specialized, &row)
+                        })
+                    }
+                    _ => {
+                        let default: Vec<Row> = matrix
+                            .iter()
+                            .filter(|row| matches!(row[0], Pat::Wild))
+                            .map(|row| row[1..].to_vec())
+                            .collect();
+                        self.is_useful(&default, rest)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Values matched by `row` which aren't matched by any row in `matrix`, at most one more
+    /// than `MAX_MISSING`.
+    fn witnesses(&self, matrix: &[Row], row: &[Pat]) -> Vec<Row> {
+        let Some((head, rest)) = row.split_first() else {
+            return if matrix.is_empty() {
+                vec![Vec::new()]
+            } else {
+                Vec::new()
+            };
+        };
+
+        // Rows starting with an alternative pattern are split into one row per alternative.
+        let matrix = expand_or(matrix);
+        match head {
+            Pat::Or(alternatives) => alternatives
+                .iter()
+                .flat_map(|alt| {
+                    let row: Row = std::iter::once(alt.clone())
+                        .chain(rest.iter().cloned())
+                        .collect();
+                    self.witnesses(&matrix, &row)
+                })
+                .take(MAX_MISSING + 1)
+                .collect(),
+            Pat::Ctor(ctor, fields) => {
+                let specialized = self.specialize(&matrix, ctor);
+                let row: Row = fields.iter().chain(rest).cloned().collect();
+                self.witnesses(&specialized, &row)
+                    .into_iter()
+                    .map(|witness| self.rebuild(ctor, witness))
+                    .collect()
+            }
+            Pat::Wild => {
+                let present: Vec<&Ctor> = matrix
+                    .iter()
+                    .filter_map(|row| match &row[0] {
+                        Pat::Ctor(ctor, _) => Some(ctor),
+                        _ => None,
+                    })
+                    .collect();
+                let all = present.first().and_then(|ctor| self.all_ctors(ctor));
+                let missing: Option<Vec<Ctor>> = all.map(|all| {
+                    all.into_iter()
+                        .filter(|ctor| !present.contains(&ctor))
+                        .collect()
+                });
+
+                match missing {
+                    // Every constructor is covered somewhere, so check each one.
+                    Some(missing) if missing.is_empty() => {
+                        let all = self.all_ctors(present[0]).unwrap_or_default();
+                        all.iter()
+                            .flat_map(|ctor| {
+                                let specialized = self.specialize(&matrix, ctor);
+                                let row: Row = std::iter::repeat(Pat::Wild)
+                                    .take(self.arity(ctor))
+                                    .chain(rest.iter().cloned())
+                                    .collect();
+                                self.witnesses(&specialized, &row)
+                                    .into_iter()
+                                    .map(|witness| self.rebuild(ctor, witness))
+                                    .collect::<Vec<_>>()
+                            })
+                            .take(MAX_MISSING + 1)
+                            .collect()
+                    }
+                    missing => {
+                        let default: Vec<Row> = matrix
+                            .iter()
+                            .filter(|row| matches!(row[0], Pat::Wild))
+                            .map(|row| row[1..].to_vec())
+                            .collect();
+                        let witnesses = self.witnesses(&default, rest);
+                        if witnesses.is_empty() {
+                            return witnesses;
+                        }
+                        let enumerable = missing.is_some();
+                        let heads = match missing {
+                            Some(missing) if !present.is_empty() => missing
+                                .into_iter()
+                                .map(|ctor| {
+                                    let fields = vec![Pat::Wild; self.arity(&ctor)];
+                                    Pat::Ctor(ctor, fields)
+                                })
+                                .collect(),
+                            _ => vec![Pat::Wild],
+                        };
+                        let mut found: Vec<Row> = heads
+                            .iter()
+                            .flat_map(|head| {
+                                witnesses.iter().map(move |witness| {
+                                    std::iter::once(head.clone())
+                                        .chain(witness.iter().cloned())
+                                        .collect()
+                                })
+                            })
+                            .take(MAX_MISSING + 1)
+                            .collect();
+                        // Constructors which are present may still be partially missing,
+                        // like `Circle(_)` when only `Circle(1)` is handled.
+                        if enumerable {
+                            let mut seen: Vec<&Ctor> = Vec::new();
+                            for ctor in present {
+                                if found.len() > MAX_MISSING {
+                                    break;
+                                }
+                                if seen.contains(&ctor) {
+                                    continue;
+                                }
+                                seen.push(ctor);
+                                let specialized = self.specialize(&matrix, ctor);
+                                let row: Row = std::iter::repeat(Pat::Wild)
+                                    .take(self.arity(ctor))
+                                    .chain(rest.iter().cloned())
+                                    .collect();
+                                found.extend(
+                                    self.witnesses(&specialized, &row)
+                                        .into_iter()
+                                        .map(|witness| self.rebuild(ctor, witness)),
+                                );
+                            }
+                            found.truncate(MAX_MISSING + 1);
+                        }
+                        found
+                    }
+                }
+            }
+        }
+    }
+
+    /// The rows which match `ctor`, with its fields spliced in place of the first column.
+    fn specialize(&self, matrix: &[Row], ctor: &Ctor) -> Vec<Row> {
+        matrix
+            .iter()
+            .filter_map(|row| {
+                let fields = match &row[0] {
+                    Pat::Ctor(other, fields) if same_ctor(ctor, other) => fields.clone(),
+                    Pat::Wild => vec![Pat::Wild; self.arity(ctor)],
+                    _ => return None,
+                };
+                Some(fields.into_iter().chain(row[1..].iter().cloned()).collect())
+            })
+            .collect()
+    }
+
+    /// Undoes `specialize` on a witness, by folding the leading fields back into `ctor`.
+    fn rebuild(&self, ctor: &Ctor, mut witness: Row) -> Row {
+        let rest = witness.split_off(self.arity(ctor).min(witness.len()));
+        std::iter::once(Pat::Ctor(ctor.clone(), witness))
+            .chain(rest)
+            .collect()
+    }
+
+    fn display(&self, pat: &Pat) -> String {
+        let (ctor, fields) = match pat {
+            Pat::Wild | Pat::Or(_) => return "_".into(),
+            Pat::Ctor(ctor, fields) => (ctor, fields),
+        };
+        let fields_text = || {
+            fields
+                .iter()
+                .map(|field| self.display(field))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match ctor {
+            Ctor::Bool(true) => "True".into(),
+            Ctor::Bool(false) => "False".into(),
+            Ctor::Nil => "Nil".into(),
+            Ctor::Ok => format!("Ok({})", fields_text()),
+            Ctor::Error => format!("Error({})", fields_text()),
+            Ctor::Tuple(_) => format!("#({})", fields_text()),
+            Ctor::Variant(variant) => {
+                let mut text = String::new();
+                if let Some(module) = self.qualifiers.get(&variant.parent().id) {
+                    text.push_str(module);
+                    text.push('.');
+                }
+                text.push_str(&variant.name(self.db.upcast()));
+                if !fields.is_empty() {
+                    text.push_str(&format!("({})", fields_text()));
+                }
+                text
+            }
+            Ctor::EmptyList | Ctor::Cons => {
+                let mut elements = Vec::new();
+                let mut tail = pat;
+                while let Pat::Ctor(Ctor::Cons, fields) = tail {
+                    elements.push(self.display(&fields[0]));
+                    tail = &fields[1];
+                }
+                if !matches!(tail, Pat::Ctor(Ctor::EmptyList, _)) {
+                    elements.push("..".into());
+                }
+                format!("[{}]", elements.join(", "))
+            }
+            Ctor::Literal(text) => text.to_string(),
+            Ctor::Opaque(_) => "_".into(),
+        }
+    }
+}
+
+fn same_ctor(a: &Ctor, b: &Ctor) -> bool {
+    !matches!(a, Ctor::Opaque(_)) && a == b
+}
+
+fn expand_or(matrix: &[Row]) -> Vec<Row> {
+    let mut expanded = Vec::with_capacity(matrix.len());
+    for row in matrix {
+        match &row[0] {
+            Pat::Or(alternatives) => {
+                for alt in alternatives {
+                    let row: Row = std::iter::once(alt.clone())
+                        .chain(row[1..].iter().cloned())
+                        .collect();
+                    expanded.extend(expand_or(&[row]));
+                }
+            }
+            _ => expanded.push(row.clone()),
+        }
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+
+    use crate::def::hir_def::ModuleDefId;
+    use crate::tests::TestDB;
+    use crate::DefDatabase;
+
+    use super::check_patterns;
+
+    #[track_caller]
+    fn check(src: &str, expect: Expect) {
+        let (db, file) = TestDB::single_file(src).unwrap();
+        let scope = db.module_scope(file);
+        let mut output = Vec::new();
+        for (def, _) in scope.declarations().flatten() {
+            if let ModuleDefId::FunctionId(fn_id) = def {
+                for diag in check_patterns(&db, *fn_id) {
+                    output.push(format!("{diag:?}"));
+                }
+            }
+        }
+        expect.assert_eq(&output.join("\n"));
+    }
+
+    #[test]
+    fn exhaustive_variants() {
+        check(
+            r#"
+type Pet { Cat Dog(name: String) Fish(Int, Int) }
+fn main(pet) {
+    case pet {
+        Cat -> 1
+        Dog(name: _) -> 2
+        Fish(_, _) -> 3
+    }
+}
+"#,
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn missing_variants() {
+        check(
+            r#"
+type Pet { Cat Dog(name: String) Fish(Int, Int) }
+fn main(pet) {
+    case pet {
+        Dog(..) -> 2
+    }
+}
+"#,
+            expect![[
+                r#"InexhaustiveCase { case: Idx::<Expr>(2), missing: ["Cat", "Fish(_, _)"] }"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn missing_and_partial_variants() {
+        check(
+            r#"
+type Shape { Circle(Int) Square(Int) Point }
+fn main(s) {
+    case s {
+        Circle(1) -> 1
+    }
+}
+"#,
+            expect![[
+                r#"InexhaustiveCase { case: Idx::<Expr>(2), missing: ["Square(_)", "Point", "Circle(_)"] }"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn guards_dont_cover() {
+        check(
+            r#"
+fn main(a) {
+    case a {
+        True if a -> 1
+        False -> 2
+    }
+}
+"#,
+            expect![[r#"InexhaustiveCase { case: Idx::<Expr>(4), missing: ["True"] }"#]],
+        );
+    }
+
+    #[test]
+    fn nested_and_tuples() {
+        check(
+            r#"
+fn main(a, b) {
+    case a, b {
+        Ok(True), _ -> 1
+        Error(_), [] -> 2
+        Error(_), [_, ..] -> 3
+    }
+}
+"#,
+            expect![[r#"InexhaustiveCase { case: Idx::<Expr>(5), missing: ["Ok(False), _"] }"#]],
+        );
+    }
+
+    #[test]
+    fn lists() {
+        check(
+            r#"
+fn main(a) {
+    case a {
+        [] -> 1
+        [_] -> 2
+    }
+}
+"#,
+            expect![[r#"InexhaustiveCase { case: Idx::<Expr>(3), missing: ["[_, _, ..]"] }"#]],
+        );
+    }
+
+    #[test]
+    fn literals_need_a_catch_all() {
+        check(
+            r#"
+fn main(a) {
+    case a {
+        1 -> 1
+        2 -> 2
+        1 -> 3
+    }
+}
+"#,
+            expect![[r#"
+                UnreachableClause { case: Idx::<Expr>(4), clause: 2 }
+                InexhaustiveCase { case: Idx::<Expr>(4), missing: ["_"] }"#]],
+        );
+    }
+
+    #[test]
+    fn unreachable_clause() {
+        check(
+            r#"
+fn main(a) {
+    case a {
+        True -> 1
+        _ -> 2
+        False -> 3
+    }
+}
+"#,
+            expect!["UnreachableClause { case: Idx::<Expr>(4), clause: 2 }"],
+        );
+    }
+
+    #[test]
+    fn alternative_patterns() {
+        check(
+            r#"
+type Pet { Cat Dog Fish }
+fn main(pet) {
+    case pet {
+        Cat | Dog -> 1
+        Dog -> 2
+    }
+}
+"#,
+            expect![[r#"
+                UnreachableClause { case: Idx::<Expr>(3), clause: 1 }
+                InexhaustiveCase { case: Idx::<Expr>(3), missing: ["Fish"] }"#]],
+        );
+    }
+
+    #[test]
+    fn multi_subject_alternatives() {
+        check(
+            r#"
+fn main(a, b) {
+    case a, b {
+        _, _ -> 1
+        1, 2 | 3, 4 -> 2
+    }
+}
+"#,
+            expect!["UnreachableClause { case: Idx::<Expr>(4), clause: 1 }"],
+        );
+        check(
+            r#"
+fn main(a, b) {
+    case a, b {
+        True, _ | _, True -> 1
+        False, False -> 2
+    }
+}
+"#,
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn many_missing() {
+        check(
+            r#"
+type Colour { Red Orange Yellow Green Blue Indigo Violet Black White Grey }
+fn main(a) {
+    case a {
+        Red -> 1
+    }
+}
+"#,
+            expect![[
+                r#"InexhaustiveCase { case: Idx::<Expr>(2), missing: ["Orange", "Yellow", "Green", "Blue", "Indigo", "Violet", "Black", "White", "Grey"] }"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn clause_of_the_wrong_width() {
+        check(
+            r#"
+fn main(a, b) {
+    case a, b {
+        x, y, z ->
+    }
+}
+"#,
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn let_assert() {
+        check(
+            r#"
+fn main(a) {
+    let Ok(b) = a
+    let assert Ok(c) = a
+    let #(d, e) = #(b, c)
+    d
+}
+"#,
+            expect![[r#"InexhaustiveLet { pattern: Idx::<Pattern>(2), missing: ["Error(_)"] }"#]],
+        );
+    }
+}
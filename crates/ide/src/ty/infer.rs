@@ -1,16 +1,25 @@
-use std::{cmp::min, collections::HashMap, mem, ops::Deref, sync::Arc};
+use std::{
+    cmp::min,
+    collections::{HashMap, HashSet},
+    mem,
+    ops::Deref,
+    sync::Arc,
+};
 
 use itertools::Itertools;
 use la_arena::ArenaMap;
 use smol_str::SmolStr;
-use syntax::ast::{BinaryOpKind, LiteralKind};
+use syntax::ast::{BinaryOpKind, LiteralKind, UnaryOpKind};
 
 use crate::{
     def::{
         body::Body,
-        hir::{self, Adt, Field, ModuleDef, TypeAlias},
-        hir_def::{AdtId, FunctionId},
-        module::{self, Expr, ExprId, Pattern, PatternId, Statement, TypeRef},
+        hir::{self, Adt, Field, Module, ModuleConstant, ModuleDef, TypeAlias},
+        hir_def::{AdtId, ConstId, FunctionId, TypeAliasId},
+        module::{
+            self, BitArrayOption, Expr, ExprId, ExprOrPatternId, Pattern, PatternId, Statement,
+            TypeRef,
+        },
         resolver::{resolver_for_toplevel, ResolveResult, Resolver},
         resolver_for_expr,
     },
@@ -19,7 +28,7 @@ use crate::{
 
 use super::{display::next_letter, union_find::UnionFind, TyDatabase};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct TyVar(u32);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -33,6 +42,7 @@ enum Ty {
     Float,
     String,
     BitArray,
+    UtfCodepoint,
     Result {
         ok: TyVar,
         err: TyVar,
@@ -65,16 +75,85 @@ pub struct InferenceResult {
     expr_ty_map: ArenaMap<ExprId, super::Ty>,
     field_resolution: HashMap<ExprId, FieldResolution>,
     module_resolution: HashMap<ExprId, FileId>,
+    type_mismatches: Vec<(ExprOrPatternId, TypeMismatch)>,
+    annotation_mismatches: Vec<(PatternId, TypeMismatch)>,
+    unresolved_names: Vec<(ExprOrPatternId, UnresolvedName)>,
+    opaque_uses: Vec<(ExprOrPatternId, Adt)>,
+    call_errors: Vec<(ExprId, CallError)>,
     pub fn_ty: super::Ty,
 }
 
+/// A failed unification, recorded at the expression or pattern that caused it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeMismatch {
+    pub expected: super::Ty,
+    pub actual: super::Ty,
+}
+
+/// Arguments of a call, or fields of a record update, that don't fit the parameters of the
+/// callee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallError {
+    /// The call or record update the argument belongs to.
+    pub call: ExprId,
+    pub kind: CallErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallErrorKind {
+    WrongArity { expected: usize, found: usize },
+    UnknownLabel(SmolStr),
+    DuplicateLabel(SmolStr),
+    PositionalAfterLabelled,
+    UnlabelledUpdateField,
+}
+
+/// A name in a function body that could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnresolvedName {
+    Variable(SmolStr),
+    Constructor(SmolStr),
+    Module(SmolStr),
+    ModuleMember {
+        module: SmolStr,
+        name: SmolStr,
+    },
+    Field {
+        constructor: SmolStr,
+        label: SmolStr,
+    },
+}
+
 impl InferenceResult {
+    fn unknown() -> Self {
+        Self {
+            pattern_ty_map: ArenaMap::new(),
+            expr_ty_map: ArenaMap::new(),
+            field_resolution: HashMap::new(),
+            module_resolution: HashMap::new(),
+            type_mismatches: Vec::new(),
+            annotation_mismatches: Vec::new(),
+            unresolved_names: Vec::new(),
+            opaque_uses: Vec::new(),
+            call_errors: Vec::new(),
+            fn_ty: super::Ty::Unknown,
+        }
+    }
+
+    /// The type of `pattern`, unknown if it wasn't inferred because of a cycle.
     pub fn ty_for_pattern(&self, pattern: PatternId) -> super::Ty {
-        self.pattern_ty_map[pattern].clone()
+        self.pattern_ty_map
+            .get(pattern)
+            .cloned()
+            .unwrap_or(super::Ty::Unknown)
     }
 
+    /// The type of `expr`, unknown if it wasn't inferred because of a cycle.
     pub fn ty_for_expr(&self, expr: ExprId) -> super::Ty {
-        self.expr_ty_map[expr].clone()
+        self.expr_ty_map
+            .get(expr)
+            .cloned()
+            .unwrap_or(super::Ty::Unknown)
     }
 
     pub fn resolve_field(&self, expr: ExprId) -> Option<FieldResolution> {
@@ -84,21 +163,70 @@ impl InferenceResult {
     pub fn resolve_module(&self, expr: ExprId) -> Option<FileId> {
         self.module_resolution.get(&expr).cloned()
     }
+
+    pub fn type_mismatches(&self) -> impl Iterator<Item = &(ExprOrPatternId, TypeMismatch)> {
+        self.type_mismatches.iter()
+    }
+
+    /// Mismatches between a `let` or `use` annotation and the value bound to the pattern, with
+    /// the annotation as the expected type.
+    pub fn annotation_mismatches(&self) -> impl Iterator<Item = &(PatternId, TypeMismatch)> {
+        self.annotation_mismatches.iter()
+    }
+
+    pub fn type_mismatch_for_expr(&self, expr: ExprId) -> Option<&TypeMismatch> {
+        self.type_mismatch_for(expr.into())
+    }
+
+    pub fn type_mismatch_for_pattern(&self, pattern: PatternId) -> Option<&TypeMismatch> {
+        self.type_mismatch_for(pattern.into())
+    }
+
+    pub fn unresolved_names(&self) -> impl Iterator<Item = &(ExprOrPatternId, UnresolvedName)> {
+        self.unresolved_names.iter()
+    }
+
+    /// Constructors or fields of opaque types used outside of their module.
+    pub fn opaque_uses(&self) -> impl Iterator<Item = &(ExprOrPatternId, Adt)> {
+        self.opaque_uses.iter()
+    }
+
+    /// Call errors, recorded at the offending argument or, for a wrong arity, at the call.
+    pub fn call_errors(&self) -> impl Iterator<Item = &(ExprId, CallError)> {
+        self.call_errors.iter()
+    }
+
+    fn type_mismatch_for(&self, source: ExprOrPatternId) -> Option<&TypeMismatch> {
+        self.type_mismatches
+            .iter()
+            .find_map(|(src, mismatch)| (*src == source).then_some(mismatch))
+    }
 }
 
 pub(crate) fn infer_function_query(db: &dyn TyDatabase, fn_id: FunctionId) -> Arc<InferenceResult> {
+    let group = function_group(db, fn_id);
+    Arc::new(db.infer_function_group(group).get(&fn_id).unwrap().clone())
+}
+
+fn function_group(db: &dyn TyDatabase, fn_id: FunctionId) -> Vec<FunctionId> {
     let fun = db.lookup_intern_function(fn_id);
     let deps = db.dependency_order(fun.file_id);
-    let group = deps
-        .into_iter()
+    deps.into_iter()
         .find(|v| v.contains(&fn_id))
-        .expect("This is a compiler error!");
-    Arc::new(db.infer_function_group(group).get(&fn_id).unwrap().clone())
+        .expect("This is a compiler error!")
 }
 
 pub(crate) fn infer_function_group_query(
     db: &dyn TyDatabase,
     group: Vec<FunctionId>,
+) -> HashMap<FunctionId, InferenceResult> {
+    infer_group(db, &group, false)
+}
+
+fn infer_group(
+    db: &dyn TyDatabase,
+    group: &Vec<FunctionId>,
+    skip_constants: bool,
 ) -> HashMap<FunctionId, InferenceResult> {
     let mut idx = 0;
     let mut table = UnionFind::new(group.len(), |_| {
@@ -117,15 +245,19 @@ pub(crate) fn infer_function_group_query(
             db,
             table: &mut table,
             idx,
-            group: &group,
-            fn_id: *f,
+            group,
+            fn_id: Some(*f),
+            file_id: func.file_id,
             resolver,
             body: body.deref(),
             body_ctx: BodyCtx::default(),
+            expanding_aliases: Vec::new(),
+            skip_constants,
         };
         let ty = ctx.infer_function(&body);
         fn_to_ty_var.insert(*f, ty);
-        ctx.unify_var(TyVar(i as u32), ty);
+        // Recursive calls may already have constrained the function.
+        ctx.unify_expr_var(body.body_expr, TyVar(i as u32), ty);
 
         let inferred_ctx = std::mem::take(&mut ctx.body_ctx);
         fn_to_ctx.insert(*f, inferred_ctx);
@@ -134,6 +266,101 @@ pub(crate) fn infer_function_group_query(
     finish_infer(table, fn_to_ctx, fn_to_ty_var)
 }
 
+/// The type of a function from its signature alone, if every parameter and the return type are
+/// annotated.
+pub(crate) fn function_signature_query(
+    db: &dyn TyDatabase,
+    fn_id: FunctionId,
+) -> Option<super::Ty> {
+    let body = db.body(fn_id);
+    let return_ = body.return_.clone().filter(TypeRef::is_complete)?;
+    let mut params = Vec::new();
+    for (_, ty, label) in &body.params {
+        let ty = ty.clone().filter(TypeRef::is_complete)?;
+        params.push((label.clone(), ty));
+    }
+
+    let loc = db.lookup_intern_function(fn_id);
+    let mut table = UnionFind::new(0, |_| Ty::Unknown { idx: 0 });
+    let mut ctx = InferCtx {
+        db,
+        table: &mut table,
+        idx: 0,
+        group: &Vec::new(),
+        fn_id: Some(fn_id),
+        file_id: loc.file_id,
+        resolver: resolver_for_toplevel(db.upcast(), loc.file_id),
+        body: body.deref(),
+        body_ctx: BodyCtx::default(),
+        expanding_aliases: Vec::new(),
+        skip_constants: false,
+    };
+    let mut env = HashMap::new();
+    let params = params
+        .into_iter()
+        .map(|(label, ty)| (label, ctx.make_ty_from_typeref(ty, &mut env)))
+        .collect();
+    let return_ = ctx.make_ty_from_typeref(return_, &mut env);
+    let ty = Ty::Function { params, return_ }.intern(&mut ctx);
+    let aliases = mem::take(&mut ctx.body_ctx.aliases);
+
+    let mut collector = Collector::new(&mut table, aliases);
+    Some(collector.collect(ty))
+}
+
+pub(crate) fn infer_const_query(db: &dyn TyDatabase, const_id: ConstId) -> Arc<InferenceResult> {
+    let body = db.const_body(const_id);
+    let loc = db.lookup_intern_const(const_id);
+    let mut table = UnionFind::new(0, |_| Ty::Unknown { idx: 0 });
+    let mut ctx = InferCtx {
+        db,
+        table: &mut table,
+        idx: 0,
+        group: &Vec::new(),
+        fn_id: None,
+        file_id: loc.file_id,
+        resolver: resolver_for_toplevel(db.upcast(), loc.file_id),
+        body: body.deref(),
+        body_ctx: BodyCtx::default(),
+        expanding_aliases: Vec::new(),
+        skip_constants: false,
+    };
+    let ty = ctx.infer_const(&body);
+    let body_ctx = mem::take(&mut ctx.body_ctx);
+
+    let aliases = body_ctx.aliases.clone();
+    let mut collector = Collector::new(&mut table, aliases);
+    Arc::new(collect_result(&mut collector, body_ctx, ty))
+}
+
+/// Constants referring to each other (possibly through a function) can't be typed.
+pub(crate) fn infer_const_recover(
+    _db: &dyn TyDatabase,
+    _cycle: &[String],
+    _const_id: &ConstId,
+) -> Arc<InferenceResult> {
+    Arc::new(InferenceResult::unknown())
+}
+
+/// Functions only take part in a cycle through a constant, so they are inferred again with the
+/// constants left unknown to keep their diagnostics.
+pub(crate) fn infer_function_recover(
+    db: &dyn TyDatabase,
+    _cycle: &[String],
+    fn_id: &FunctionId,
+) -> Arc<InferenceResult> {
+    let group = function_group(db, *fn_id);
+    Arc::new(infer_group(db, &group, true).remove(fn_id).unwrap())
+}
+
+pub(crate) fn infer_function_group_recover(
+    db: &dyn TyDatabase,
+    _cycle: &[String],
+    group: &[FunctionId],
+) -> HashMap<FunctionId, InferenceResult> {
+    infer_group(db, &group.to_vec(), true)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FieldResolution {
     Field(Field),
@@ -147,18 +374,41 @@ struct BodyCtx {
 
     field_resolution: HashMap<ExprId, FieldResolution>,
     module_resolution: HashMap<ExprId, FileId>,
+
+    // (source, expected, actual)
+    type_mismatches: Vec<(ExprOrPatternId, TyVar, TyVar)>,
+    // (bound pattern, annotation, actual)
+    annotation_mismatches: Vec<(PatternId, TyVar, TyVar)>,
+    unresolved_names: Vec<(ExprOrPatternId, UnresolvedName)>,
+    opaque_uses: Vec<(ExprOrPatternId, Adt)>,
+    call_errors: Vec<(ExprId, CallError)>,
+
+    /// Variables quantified over in the type of `let` bound locals, each use instantiates them
+    /// afresh.
+    schemes: HashMap<PatternId, Vec<u32>>,
+
+    /// Types that were written as an alias, so they can be displayed as such. Only the variable
+    /// of the written type and those bound to it keep the name, not everything unified with it.
+    aliases: HashMap<TyVar, (TypeAliasId, Vec<TyVar>)>,
 }
 struct InferCtx<'db> {
     db: &'db dyn TyDatabase,
     body_ctx: BodyCtx,
     idx: u32,
-    fn_id: FunctionId,
+    /// `None` when inferring a constant.
+    fn_id: Option<FunctionId>,
+    /// The module the body lives in.
+    file_id: FileId,
     // The resolver is not kept up to date always, it's there to allow infer_pattern to work comfortably.
     // If it's important to have a new resolver, use a new one
     resolver: Resolver,
     group: &'db Vec<FunctionId>,
     body: &'db Body,
     table: &'db mut UnionFind<Ty>,
+    /// Aliases currently being expanded, to not loop forever on cyclic ones.
+    expanding_aliases: Vec<TypeAliasId>,
+    /// Whether constants are left unknown, to recover from a cycle running through one.
+    skip_constants: bool,
 }
 
 impl<'db> InferCtx<'db> {
@@ -211,10 +461,7 @@ impl<'db> InferCtx<'db> {
                 let mut pars = Vec::new();
 
                 for param in params.deref().iter() {
-                    let par_var = self.new_ty_var();
-                    let par_ty = self.make_ty_from_typeref(param.clone(), env);
-                    self.unify_var(par_var, par_ty);
-                    pars.push(par_var);
+                    pars.push(self.make_ty_from_typeref(param.clone(), env));
                 }
                 let module_def = match module {
                     Some(module) => self.resolver.resolve_module(&module).and_then(|m| {
@@ -231,19 +478,7 @@ impl<'db> InferCtx<'db> {
                             adt_id: adt.id,
                         },
                         ResolveResult::TypeAlias(alias) => {
-                            let data = TypeAlias { id: alias.id }.data(self.db.upcast());
-                            let type_alias = self.db.lookup_intern_type_alias(alias.id);
-                            if let Some(ty) = data.body {
-                                let resolver = std::mem::replace(
-                                    &mut self.resolver,
-                                    resolver_for_toplevel(self.db.upcast(), type_alias.file_id),
-                                );
-                                let ty = self.make_ty_from_typeref(ty, env);
-                                let _ = std::mem::replace(&mut self.resolver, resolver);
-                                return ty;
-                            }
-                            self.idx += 1;
-                            Ty::Unknown { idx: self.idx }
+                            return self.expand_alias(alias, pars);
                         }
                         _ => {
                             self.idx += 1;
@@ -255,17 +490,18 @@ impl<'db> InferCtx<'db> {
                         "Float" => Ty::Float,
                         "String" => Ty::String,
                         "BitArray" => Ty::BitArray,
+                        "UtfCodepoint" => Ty::UtfCodepoint,
                         "Bool" => Ty::Bool,
                         "Nil" => Ty::Nil,
                         "List" => Ty::List {
                             of: self.make_ty_from_typeref(
-                                params.get(0).unwrap_or(&TypeRef::Unknown).clone(),
+                                params.first().unwrap_or(&TypeRef::Unknown).clone(),
                                 env,
                             ),
                         },
                         "Result" => {
                             let ok = self.make_ty_from_typeref(
-                                params.get(0).unwrap_or(&TypeRef::Unknown).clone(),
+                                params.first().unwrap_or(&TypeRef::Unknown).clone(),
                                 env,
                             );
                             let err = self.make_ty_from_typeref(
@@ -288,7 +524,7 @@ impl<'db> InferCtx<'db> {
                     Some(var) => *var,
                     None => {
                         let var = self.new_ty_var();
-                        self.unify_var_ty(var, Ty::Unknown { idx: var.0 });
+                        self.bind_var_ty(var, Ty::Unknown { idx: var.0 });
                         env.insert(name, var);
                         var
                     }
@@ -298,18 +534,55 @@ impl<'db> InferCtx<'db> {
         ty.intern(self)
     }
 
+    fn expand_alias(&mut self, alias: TypeAlias, params: Vec<TyVar>) -> TyVar {
+        let data = alias.data(self.db.upcast());
+        // Cyclic aliases don't expand to anything, they're reported by the diagnostics.
+        let Some(body) = data
+            .body
+            .filter(|_| !self.expanding_aliases.contains(&alias.id))
+        else {
+            return self.new_ty_var();
+        };
+
+        // The body only sees the alias' own parameters, not the generics of the use site.
+        let mut env = HashMap::new();
+        for (param, ty) in data.params.iter().zip(&params) {
+            if let TypeRef::Generic { name } = param {
+                env.insert(name.clone(), *ty);
+            }
+        }
+        let resolver = mem::replace(
+            &mut self.resolver,
+            resolver_for_toplevel(self.db.upcast(), alias.module(self.db.upcast()).id),
+        );
+        self.expanding_aliases.push(alias.id);
+        let ty = self.make_ty_from_typeref(body.clone(), &mut env);
+        self.expanding_aliases.pop();
+        self.resolver = resolver;
+
+        // `type Id(a) = a` would otherwise rename every use of `a`.
+        if !matches!(body, TypeRef::Generic { .. }) {
+            self.body_ctx
+                .aliases
+                .entry(ty)
+                .or_insert((alias.id, params));
+        }
+        ty
+    }
+
+    /// Instantiates an inferred type. Its generics are quantified over, so a fresh `env` gives
+    /// fresh variables for every use of a function, constructor or generic type.
     // ToDo: Add context file_id to make proper resolver!
     fn make_type(&mut self, ty: super::Ty, env: &mut HashMap<SmolStr, TyVar>) -> TyVar {
         let ty = match ty {
             super::Ty::Unknown => return self.new_ty_var(),
             super::Ty::Hole => return self.new_ty_var(),
-            // this is not correct, same name obviously doesnt have same type e.g fn a(a:a) {a} and fn b(b: a} {b} dont have the same type!
             super::Ty::Generic { name } => {
                 return match env.get(&name) {
                     Some(var) => *var,
                     None => {
                         let var = self.new_ty_var();
-                        self.unify_var_ty(var, Ty::Unknown { idx: var.0 });
+                        self.bind_var_ty(var, Ty::Unknown { idx: var.0 });
                         env.insert(name, var);
                         var
                     }
@@ -321,6 +594,7 @@ impl<'db> InferCtx<'db> {
             super::Ty::Float => Ty::Float,
             super::Ty::String => Ty::String,
             super::Ty::BitArray => Ty::BitArray,
+            super::Ty::UtfCodepoint => Ty::UtfCodepoint,
             super::Ty::Result { ok, err } => {
                 let ok = self.make_type(ok.deref().clone(), env);
                 let err = self.make_type(err.deref().clone(), env);
@@ -333,14 +607,9 @@ impl<'db> InferCtx<'db> {
             super::Ty::Function { params, return_ } => {
                 let mut pars = Vec::new();
                 for (label, param) in params.deref().iter() {
-                    let par_var = self.new_ty_var();
-                    let par_ty = self.make_type(param.clone(), env);
-                    self.unify_var(par_var, par_ty);
-                    pars.push((label.clone(), par_var));
+                    pars.push((label.clone(), self.make_type(param.clone(), env)));
                 }
-                let ret = self.new_ty_var();
-                let ret_ty = self.make_type(return_.deref().clone(), env);
-                self.unify_var(ret, ret_ty);
+                let ret = self.make_type(return_.deref().clone(), env);
                 Ty::Function {
                     params: pars,
                     return_: ret,
@@ -358,16 +627,29 @@ impl<'db> InferCtx<'db> {
                 let mut pars = Vec::new();
 
                 for param in params.deref().iter() {
-                    let par_var = self.new_ty_var();
-                    let par_ty = self.make_type(param.clone(), env);
-                    self.unify_var(par_var, par_ty);
-                    pars.push(par_var);
+                    pars.push(self.make_type(param.clone(), env));
                 }
                 Ty::Adt {
                     adt_id,
                     generic_params: pars,
                 }
             }
+            super::Ty::Alias {
+                alias_id,
+                params,
+                ty,
+            } => {
+                let var = self.make_type(ty.deref().clone(), env);
+                let params = params
+                    .iter()
+                    .map(|param| self.make_type(param.clone(), env))
+                    .collect();
+                self.body_ctx
+                    .aliases
+                    .entry(var)
+                    .or_insert((alias_id, params));
+                return var;
+            }
         };
         TyVar(self.table.push(ty))
     }
@@ -379,7 +661,7 @@ impl<'db> InferCtx<'db> {
             let pat_ty = self.ty_for_pattern(*param);
             if let Some(ty) = ty {
                 let param_ty = self.make_ty_from_typeref(ty.clone(), &mut env);
-                self.unify_var(pat_ty, param_ty);
+                self.bind_var(pat_ty, param_ty);
             }
             param_tys.push((label.clone(), pat_ty));
         }
@@ -389,7 +671,7 @@ impl<'db> InferCtx<'db> {
             .map(|r| self.make_ty_from_typeref(r.clone(), &mut env));
         let body_ty = self.infer_expr(body.body_expr);
         if let Some(ret) = return_ {
-            self.unify_var(ret, body_ty);
+            self.unify_return_var(body.body_expr, ret, body_ty);
         }
 
         Ty::Function {
@@ -399,6 +681,24 @@ impl<'db> InferCtx<'db> {
         .intern(self)
     }
 
+    fn infer_const(&mut self, body: &Body) -> TyVar {
+        let value_ty = self.infer_expr(body.body_expr);
+        let Some(annotation) = &body.return_ else {
+            return value_ty;
+        };
+        let annotation_ty = self.make_ty_from_typeref(annotation.clone(), &mut HashMap::new());
+        self.unify_expr_var(body.body_expr, annotation_ty, value_ty);
+        annotation_ty
+    }
+
+    /// Constants have no local scopes, so the toplevel resolver is all they need.
+    fn resolver_for_expr(&self, expr: ExprId) -> Resolver {
+        match self.fn_id {
+            Some(fn_id) => resolver_for_expr(self.db.upcast(), fn_id, expr),
+            None => self.resolver.clone(),
+        }
+    }
+
     fn infer_stmts(&mut self, stmts: Vec<Statement>) -> TyVar {
         let iter = stmts.into_iter();
         self.infer_stmts_iter(iter)
@@ -408,66 +708,85 @@ impl<'db> InferCtx<'db> {
         let mut last = self.new_ty_var();
         while let Some(stmt) = stmts.next() {
             match stmt {
-                Statement::Let { pattern, body } => {
-                    //infer pat
-                    let infered = self.infer_expr(body);
+                Statement::Let {
+                    pattern,
+                    annotation,
+                    body,
+                    ..
+                } => {
+                    let outer_vars = self.table.len();
+                    let mut infered = self.infer_expr(body);
+                    // The binding has the annotated type, even if the value doesn't match it.
+                    if let Some(annotation) = annotation {
+                        let annotation_ty =
+                            self.make_ty_from_typeref(annotation, &mut HashMap::new());
+                        self.unify_annotation_var(pattern, annotation_ty, infered);
+                        infered = annotation_ty;
+                    }
                     last = self.infer_pattern(pattern, infered);
-                    // let pat_var = self.ty_for_pattern(pattern);
-                    self.unify_var(last, infered);
+                    if let Pattern::Variable { .. } = self.body[pattern] {
+                        self.generalise(pattern, last, outer_vars);
+                    }
                 }
                 Statement::Expr { expr } => last = self.infer_expr(expr),
                 Statement::Use { patterns, expr } => {
+                    // `use a <- f(x)` calls `f(x, fn(a) { <rest of the block> })`.
+                    let (func, args) = match &self.body[expr] {
+                        Expr::Call { func, args } => (*func, args.clone()),
+                        _ => (expr, Vec::new()),
+                    };
+                    let fun_ty = self.infer_expr(func);
+                    let mut arg_tys = Vec::new();
+                    for (label, arg) in &args {
+                        arg_tys.push((label.clone(), self.infer_expr(*arg)));
+                    }
+
                     let mut params = Vec::new();
-                    for pat in patterns {
-                        let new_var = self.new_ty_var();
-                        let pat_ty = self.infer_pattern(pat, new_var);
-                        params.push((None, pat_ty));
+                    let mut annotations = Vec::new();
+                    let mut env = HashMap::new();
+                    for (pat, annotation) in patterns {
+                        let param_ty = self.new_ty_var();
+                        // Like with `let`, the binding has the annotated type.
+                        let bound_ty = match annotation {
+                            Some(annotation) => {
+                                let annotation_ty = self.make_ty_from_typeref(annotation, &mut env);
+                                annotations.push((pat, annotation_ty, param_ty));
+                                annotation_ty
+                            }
+                            None => param_ty,
+                        };
+                        self.infer_pattern(pat, bound_ty);
+                        params.push((None, param_ty));
                     }
-                    let cb_tail = self.infer_stmts(stmts.collect());
+                    let cb_return = self.new_ty_var();
                     let cb_ty = Ty::Function {
                         params,
-                        return_: cb_tail,
-                    };
-                    let cb_tyvar = self.new_ty_var();
-                    self.unify_var_ty(cb_tyvar, cb_ty);
-                    match &self.body[expr] {
-                        Expr::Call { func, args } => {
-                            let mut arg_tys = Vec::new();
-                            for (_, arg) in args {
-                                let param_ty = self.new_ty_var();
-                                arg_tys.push((None, param_ty));
-                                let arg_ty = self.infer_expr(*arg);
-                                self.unify_var(arg_ty, param_ty);
-                            }
-                            arg_tys.push((None, cb_tyvar));
-                            let ret_ty = self.new_ty_var();
-                            let fun_ty = self.infer_expr(*func);
-
-                            self.unify_var_ty(
-                                fun_ty,
-                                Ty::Function {
-                                    params: arg_tys,
-                                    return_: ret_ty,
-                                },
-                            );
-                            last = ret_ty
-                        }
-                        _ => {
-                            let arg_tys = vec![(None, cb_tyvar)];
-                            let ret_ty = self.new_ty_var();
-                            let fun_ty = self.infer_expr(expr);
-
-                            self.unify_var_ty(
-                                fun_ty,
-                                Ty::Function {
-                                    params: arg_tys,
-                                    return_: ret_ty,
-                                },
-                            );
-                            last = ret_ty
-                        }
+                        return_: cb_return,
+                    }
+                    .intern(self);
+
+                    // The callback is always the last positional argument.
+                    let ret_ty = self.apply(expr, fun_ty, &args, &arg_tys, Some(cb_ty));
+                    // Annotations are checked against what the callee passes in.
+                    for (pat, annotation_ty, param_ty) in annotations {
+                        self.unify_annotation_var(pat, annotation_ty, param_ty);
+                    }
+                    if func != expr {
+                        let call_ty = self.ty_for_expr(expr);
+                        self.bind_var(call_ty, ret_ty);
                     }
 
+                    // Only now are the bound patterns known, so the rest of the
+                    // block can rely on their types.
+                    let rest: Vec<_> = stmts.collect();
+                    let tail_expr = match rest.last() {
+                        Some(Statement::Expr { expr }) => *expr,
+                        _ => expr,
+                    };
+                    let tail = self.infer_stmts(rest);
+                    // The callee may expect the callback to return a certain type.
+                    self.unify_expr_var(tail_expr, cb_return, tail);
+                    last = ret_ty;
                     break;
                 }
             }
@@ -478,7 +797,7 @@ impl<'db> InferCtx<'db> {
     fn infer_expr(&mut self, e: ExprId) -> TyVar {
         let placeholder_ty = self.ty_for_expr(e);
         let ty = self.infer_expr_inner(e);
-        self.unify_var(placeholder_ty, ty);
+        self.bind_var(placeholder_ty, ty);
         ty
     }
 
@@ -486,18 +805,34 @@ impl<'db> InferCtx<'db> {
         match &self.body[tgt_expr] {
             Expr::Missing => self.new_ty_var(),
             Expr::Hole => self.new_ty_var(),
+            Expr::Todo { message } | Expr::Panic { message } => {
+                if let Some(message) = *message {
+                    let ty = self.infer_expr(message);
+                    let string = Ty::String.intern(self);
+                    self.unify_expr_var(message, string, ty);
+                }
+                // Never returns, so it can stand in for any type.
+                self.new_ty_var()
+            }
             Expr::Literal(lit) => match lit {
                 LiteralKind::Int => Ty::Int,
                 LiteralKind::Float => Ty::Float,
                 LiteralKind::String => Ty::String,
             }
             .intern(self),
-            Expr::BitArray => Ty::BitArray.intern(self),
+            Expr::BitArray { segments } => {
+                for segment in segments.clone() {
+                    let expected = self.segment_ty(&segment.options, || {
+                        matches!(self.body[segment.value], Expr::Literal(LiteralKind::String))
+                    });
+                    let ty = self.infer_expr(segment.value);
+                    self.unify_expr_var(segment.value, expected, ty);
+                }
+                Ty::BitArray.intern(self)
+            }
             Expr::Block { stmts } => {
-                let old_resolver = mem::replace(
-                    &mut self.resolver,
-                    resolver_for_expr(self.db.upcast(), self.fn_id, tgt_expr),
-                );
+                let resolver = self.resolver_for_expr(tgt_expr);
+                let old_resolver = mem::replace(&mut self.resolver, resolver);
                 let ty = self.infer_stmts(stmts.clone());
                 self.resolver = old_resolver;
                 ty
@@ -508,57 +843,82 @@ impl<'db> InferCtx<'db> {
                 let Some(op) = op else {
                     return self.new_ty_var();
                 };
+                let (left, right) = (*left, *right);
                 match op {
                     BinaryOpKind::IntAdd
                     | BinaryOpKind::IntSub
                     | BinaryOpKind::IntMul
                     | BinaryOpKind::IntDiv
                     | BinaryOpKind::IntMod => {
-                        self.unify_var_ty(lhs_ty, Ty::Int);
-                        self.unify_var(lhs_ty, rhs_ty);
-                        lhs_ty
+                        let int = Ty::Int.intern(self);
+                        self.unify_expr_var(left, int, lhs_ty);
+                        self.unify_expr_var(right, int, rhs_ty);
+                        int
                     }
                     BinaryOpKind::FloatAdd
                     | BinaryOpKind::FloatSub
                     | BinaryOpKind::FloatMul
                     | BinaryOpKind::FloatDiv => {
-                        self.unify_var_ty(lhs_ty, Ty::Float);
-                        self.unify_var(lhs_ty, rhs_ty);
-                        lhs_ty
+                        let float = Ty::Float.intern(self);
+                        self.unify_expr_var(left, float, lhs_ty);
+                        self.unify_expr_var(right, float, rhs_ty);
+                        float
                     }
                     BinaryOpKind::IntGT
                     | BinaryOpKind::IntLT
                     | BinaryOpKind::IntGTE
                     | BinaryOpKind::IntLTE => {
-                        self.unify_var_ty(lhs_ty, Ty::Int);
-                        self.unify_var(lhs_ty, rhs_ty);
-                        let bin_var = self.new_ty_var();
-                        self.unify_var_ty(bin_var, Ty::Bool);
-                        bin_var
+                        let int = Ty::Int.intern(self);
+                        self.unify_expr_var(left, int, lhs_ty);
+                        self.unify_expr_var(right, int, rhs_ty);
+                        Ty::Bool.intern(self)
                     }
                     BinaryOpKind::FloatGT
                     | BinaryOpKind::FloatLT
                     | BinaryOpKind::FloatGTE
                     | BinaryOpKind::FloatLTE => {
-                        self.unify_var_ty(lhs_ty, Ty::Float);
-                        self.unify_var(lhs_ty, rhs_ty);
-                        let bin_var = self.new_ty_var();
-                        self.unify_var_ty(bin_var, Ty::Bool);
-                        bin_var
+                        let float = Ty::Float.intern(self);
+                        self.unify_expr_var(left, float, lhs_ty);
+                        self.unify_expr_var(right, float, rhs_ty);
+                        Ty::Bool.intern(self)
+                    }
+                    BinaryOpKind::Eq | BinaryOpKind::NotEq => {
+                        self.unify_expr_var(right, lhs_ty, rhs_ty);
+                        Ty::Bool.intern(self)
                     }
-                    BinaryOpKind::Eq => {
-                        let bool = self.new_ty_var();
-                        self.unify_var_ty(bool, Ty::Bool);
-                        self.unify_var(lhs_ty, rhs_ty);
+                    BinaryOpKind::And | BinaryOpKind::Or => {
+                        let bool = Ty::Bool.intern(self);
+                        self.unify_expr_var(left, bool, lhs_ty);
+                        self.unify_expr_var(right, bool, rhs_ty);
                         bool
                     }
                     BinaryOpKind::Concat => {
-                        self.unify_var_ty(lhs_ty, Ty::String);
-                        self.unify_var(lhs_ty, rhs_ty);
-                        lhs_ty
+                        let string = Ty::String.intern(self);
+                        self.unify_expr_var(left, string, lhs_ty);
+                        self.unify_expr_var(right, string, rhs_ty);
+                        string
                     }
                 }
             }
+            Expr::Unary { expr, op } => {
+                let expr = *expr;
+                let ty = self.infer_expr(expr);
+                let Some(op) = op else {
+                    return ty;
+                };
+                let expected = match op {
+                    UnaryOpKind::Not => Ty::Bool,
+                    // `-` only negates ints, floats need `-.` unless it is a literal like `-1.5`.
+                    UnaryOpKind::Negate => match self.body[expr] {
+                        Expr::Literal(LiteralKind::Float) => Ty::Float,
+                        _ => Ty::Int,
+                    },
+                    UnaryOpKind::FloatNegate => Ty::Float,
+                }
+                .intern(self);
+                self.unify_expr_var(expr, expected, ty);
+                expected
+            }
             Expr::Tuple { fields } => {
                 let mut field_ty = Vec::new();
                 for field in fields.iter() {
@@ -569,108 +929,68 @@ impl<'db> InferCtx<'db> {
             Expr::Spread { expr } => {
                 let sp_ty = self.infer_expr(*expr);
                 let of = self.new_ty_var();
-                self.unify_var_ty(sp_ty, Ty::List { of });
+                let list_ty = Ty::List { of }.intern(self);
+                self.unify_expr_var(*expr, list_ty, sp_ty);
                 of
             }
             Expr::Pipe { left, right } => {
                 let arg_ty = self.infer_expr(*left);
-
                 let ret_ty = self.new_ty_var();
 
-                // Ugly hack to notify Expr call of
-                match &self.body[*right] {
+                let (fun_ty, inserted) = match &self.body[*right] {
+                    // `a |> f(b)` is `f(a, b)`, unless `f` is known to take exactly the
+                    // arguments given and its result is called with `a` instead. Captures like
+                    // `f(_, b)` are functions already.
                     Expr::Call { func, args } => {
+                        let args = args.clone();
                         let func_ty = self.infer_expr(*func);
-                        let ty = self.table.get_mut(func_ty.0).clone();
-
-                        for (_, arg) in args {
-                            self.infer_expr(*arg);
-                        }
-
-                        match ty {
-                            Ty::Function { params, return_ } => {
-                                if params.len() > args.len() && !params.is_empty() {
-                                    self.unify_var(arg_ty, params[0].1);
-                                }
-                                return return_;
-                            }
-                            _ => {
-                                //ToDo: Diagnostics
-                            }
-                        }
-                        //infer func expr, check if amount of params is the same as args, then unify left
-                    }
-                    _ => {
-                        //infer right, and unify like int he old times
+                        let is_capture = args
+                            .iter()
+                            .any(|(_, arg)| matches!(self.body[*arg], Expr::Hole));
+                        let insert = !is_capture
+                            && !matches!(
+                                self.table.get_mut(func_ty.0),
+                                Ty::Function { params, .. } if params.len() == args.len()
+                            );
+                        let piped = insert.then_some((*left, arg_ty));
+                        let call_ty = self.infer_call(*right, func_ty, &args, piped);
+                        let placeholder_ty = self.ty_for_expr(*right);
+                        self.bind_var(placeholder_ty, call_ty);
+                        (call_ty, insert)
                     }
+                    _ => (self.infer_expr(*right), false),
+                };
+                if inserted {
+                    return fun_ty;
                 }
 
-                let fun_ty = self.infer_expr(*right);
-
-                self.unify_var_ty(
-                    fun_ty,
-                    Ty::Function {
-                        params: vec![(None, arg_ty)],
-                        return_: ret_ty,
-                    },
-                );
+                let expected = Ty::Function {
+                    params: vec![(None, arg_ty)],
+                    return_: ret_ty,
+                }
+                .intern(self);
+                self.unify_expr_var(*right, expected, fun_ty);
                 ret_ty
             }
             Expr::Call { func, args } => {
-                let mut arg_tys = Vec::new();
-                let mut hole = None;
-                // add(_, 1) => fn(_hole) { add(_hole, 1) }
-                let ret_ty = self.new_ty_var();
                 let fun_ty = self.infer_expr(*func);
-
-                for (label, arg) in args {
-                    match self.body[*arg] {
-                        Expr::Hole => {
-                            let var = self.infer_expr(*arg);
-                            hole = Some(var);
-                            arg_tys.push((label.clone(), var));
-                        }
-                        // ..object
-                        Expr::Spread { expr } => {
-                            let expr = self.infer_expr(expr);
-                            self.unify_var(ret_ty, expr);
-                        }
-                        _ => {
-                            arg_tys.push((label.clone(), self.infer_expr(*arg)));
-                        }
-                    }
-                }
-
-                self.unify_var_ty(
-                    fun_ty,
-                    Ty::Function {
-                        params: arg_tys,
-                        return_: ret_ty,
-                    },
-                );
-
-                if let Some(hole) = hole {
-                    let new_ty = self.new_ty_var();
-                    self.unify_var_ty(
-                        new_ty,
-                        Ty::Function {
-                            params: vec![(None, hole)],
-                            return_: ret_ty,
-                        },
-                    );
-                    return new_ty;
-                }
-                ret_ty
+                self.infer_call(tgt_expr, fun_ty, args, None)
             }
             Expr::Variable(name) => {
                 let db = self.db;
                 //
-                let resolver = resolver_for_expr(db.upcast(), self.fn_id, tgt_expr);
+                let resolver = self.resolver_for_expr(tgt_expr);
                 match resolver.resolve_name(name) {
                     Some(ResolveResult::Local(local)) => {
-                        let pattern = self.body_ctx.pattern_to_ty.get(local.pat_id);
+                        let pattern = self.body_ctx.pattern_to_ty.get(local.pat_id).copied();
                         match pattern {
-                            Some(ty_var) => *ty_var,
+                            Some(ty_var) => match self.body_ctx.schemes.get(&local.pat_id) {
+                                Some(vars) => {
+                                    let vars = vars.clone();
+                                    self.instantiate(ty_var, &vars, &mut HashMap::new())
+                                }
+                                None => ty_var,
+                            },
                             None => self.new_ty_var(),
                         }
                     }
@@ -694,7 +1014,12 @@ impl<'db> InferCtx<'db> {
                             }
                         }
                     }
-                    // No resolution found, user error!?! report diagnostic!
+                    Some(ResolveResult::ModuleConstant(it)) => self.type_from_constant(it),
+                    // Module accessors are resolved by the enclosing field access.
+                    None if resolver.resolve_module(name).is_none() => {
+                        self.unresolved(tgt_expr, UnresolvedName::Variable(name.clone()));
+                        self.new_ty_var()
+                    }
                     _ => self.new_ty_var(),
                 }
             }
@@ -706,11 +1031,18 @@ impl<'db> InferCtx<'db> {
                 let ret = self.new_ty_var();
 
                 for clause in clauses.iter() {
-                    for (pat, ty) in clause.patterns.iter().zip(subject_tys.iter()) {
-                        self.infer_pattern(*pat, *ty);
+                    for row in clause.rows() {
+                        for (pat, ty) in row.iter().zip(subject_tys.iter()) {
+                            self.infer_pattern(*pat, *ty);
+                        }
+                    }
+                    if let Some(guard) = clause.guard {
+                        let guard_ty = self.infer_expr(guard);
+                        let bool_ty = Ty::Bool.intern(self);
+                        self.unify_expr_var(guard, bool_ty, guard_ty);
                     }
                     let expr_ty = self.infer_expr(clause.expr);
-                    self.unify_var(expr_ty, ret);
+                    self.unify_expr_var(clause.expr, ret, expr_ty);
                 }
                 ret
             }
@@ -730,12 +1062,16 @@ impl<'db> InferCtx<'db> {
                 } = adt.clone()
                 {
                     let adt = Adt { id: adt_id };
+                    if self.check_opaque(tgt_expr, adt) {
+                        return field_var;
+                    }
                     if let Some(field) = adt.common_fields(self.db.upcast()).get(label_name) {
                         let mut env = HashMap::new();
                         let uninstantiated_params = adt.generic_params(self.db.upcast());
                         for (uparam, inst) in uninstantiated_params.iter().zip(generic_params) {
+                            // Each generic parameter gets a fresh var here.
                             let ty = self.make_ty_from_typeref(uparam.clone(), &mut env);
-                            self.unify_var(ty, inst);
+                            self.bind_var(ty, inst);
                         }
                         self.body_ctx
                             .field_resolution
@@ -773,6 +1109,7 @@ impl<'db> InferCtx<'db> {
                             }
                             // ToDo: Add type to adt in hir and fix this.
                             ResolveResult::Variant(it) => {
+                                self.check_opaque(tgt_expr, it.parent());
                                 let (ty, params) = self.type_from_variant(it);
                                 let var = if !params.is_empty() {
                                     Ty::Function {
@@ -793,8 +1130,22 @@ impl<'db> InferCtx<'db> {
                             ResolveResult::Module(_) => {}
                             ResolveResult::Adt(_) => {}
                             ResolveResult::TypeAlias(_) => {}
-                            ResolveResult::ModuleConstant(_) => {}
+                            ResolveResult::ModuleConstant(it) => {
+                                self.body_ctx.field_resolution.insert(
+                                    tgt_expr,
+                                    FieldResolution::ModuleDef(ModuleDef::ModuleConstant(it)),
+                                );
+                                return self.type_from_constant(it);
+                            }
                         }
+                    } else {
+                        self.unresolved(
+                            tgt_expr,
+                            UnresolvedName::ModuleMember {
+                                module: base_string.clone(),
+                                name: label_name.clone(),
+                            },
+                        );
                     }
                 }
                 field_var
@@ -813,10 +1164,53 @@ impl<'db> InferCtx<'db> {
                     };
                     return *indexed;
                 }
-                return self.new_ty_var();
+                self.new_ty_var()
+            }
+            Expr::RecordUpdate {
+                constructor,
+                base,
+                fields,
+            } => {
+                let (constructor, base, fields) = (*constructor, *base, fields.clone());
+                let constructor_name = match &self.body[constructor] {
+                    Expr::VariantLiteral { name } => name.clone(),
+                    Expr::FieldAccess { label_name, .. } => label_name.clone(),
+                    _ => SmolStr::default(),
+                };
+                let constructor_ty = self.infer_expr(constructor);
+                let base_ty = self.infer_expr(base);
+                let (params, update_ty) = match self.table.get_mut(constructor_ty.0).clone() {
+                    Ty::Function { params, return_ } => {
+                        self.unify_expr_var(base, return_, base_ty);
+                        (Some(params), return_)
+                    }
+                    _ => (None, base_ty),
+                };
+                for (label, value) in fields {
+                    let value_ty = self.infer_expr(value);
+                    let Some(label) = label else {
+                        let kind = CallErrorKind::UnlabelledUpdateField;
+                        self.push_call_error(value, tgt_expr, kind);
+                        continue;
+                    };
+                    let Some(params) = &params else {
+                        continue;
+                    };
+                    match params.iter().find(|(it, _)| it.as_ref() == Some(&label)) {
+                        Some((_, param_ty)) => self.unify_expr_var(value, *param_ty, value_ty),
+                        None => self.unresolved(
+                            value,
+                            UnresolvedName::Field {
+                                constructor: constructor_name.clone(),
+                                label,
+                            },
+                        ),
+                    }
+                }
+                update_ty
             }
             Expr::VariantLiteral { name } => {
-                let (ty, params) = self.resolve_variant(name);
+                let (ty, params) = self.resolve_variant(tgt_expr.into(), name);
                 if !params.is_empty() {
                     Ty::Function {
                         params,
@@ -831,20 +1225,35 @@ impl<'db> InferCtx<'db> {
                 let of_ty = self.new_ty_var();
                 for elem in elements {
                     let elem_ty = self.infer_expr(*elem);
-                    self.unify_var(elem_ty, of_ty);
+                    self.unify_expr_var(*elem, of_ty, elem_ty);
                 }
                 Ty::List { of: of_ty }.intern(self)
             }
-            Expr::Lambda { body, params } => {
+            Expr::Lambda {
+                body,
+                params,
+                return_,
+            } => {
                 let mut param_tys = Vec::new();
-                for param in params.clone() {
-                    let pat_ty = self.ty_for_pattern(param);
+                let mut env = HashMap::new();
+                for (param, ty) in params {
+                    let pat_ty = self.ty_for_pattern(*param);
+                    if let Some(ty) = ty {
+                        let param_ty = self.make_ty_from_typeref(ty.clone(), &mut env);
+                        self.bind_var(pat_ty, param_ty);
+                    }
                     param_tys.push((None, pat_ty));
                 }
+                let return_ = return_
+                    .as_ref()
+                    .map(|r| self.make_ty_from_typeref(r.clone(), &mut env));
                 let body_ty = self.infer_expr(*body);
+                if let Some(ret) = return_ {
+                    self.unify_return_var(*body, ret, body_ty);
+                }
                 Ty::Function {
                     params: param_tys,
-                    return_: body_ty,
+                    return_: return_.unwrap_or(body_ty),
                 }
                 .intern(self)
             }
@@ -855,15 +1264,27 @@ impl<'db> InferCtx<'db> {
         let lhs = mem::replace(self.table.get_mut(var.0), Ty::Unknown { idx: 0 });
         if let Ok(ret) = self.unify(lhs.clone(), rhs) {
             *self.table.get_mut(var.0) = ret;
-            return true;
+            true
         } else {
             *self.table.get_mut(var.0) = lhs;
-            return false;
-        };
+            false
+        }
     }
 
-    fn unify_var(&mut self, lhs: TyVar, rhs: TyVar) {
-        let _ = self.try_unify_var(lhs, rhs);
+    /// Binds `var`, which nothing has constrained yet, to `ty`. Unifying with an unknown type
+    /// can't fail, so unlike the other `unify_*` functions there's nothing to report.
+    fn bind_var(&mut self, var: TyVar, ty: TyVar) {
+        let bound = self.try_unify_var(var, ty);
+        debug_assert!(bound.is_ok());
+        // `var` is the very same type, including the alias it was written as.
+        if let Some(alias) = self.body_ctx.aliases.get(&ty).cloned() {
+            self.body_ctx.aliases.entry(var).or_insert(alias);
+        }
+    }
+
+    fn bind_var_ty(&mut self, var: TyVar, ty: Ty) {
+        let bound = self.unify_var_ty(var, ty);
+        debug_assert!(bound);
     }
 
     fn try_unify_var(&mut self, lhs: TyVar, rhs: TyVar) -> Result<(), ()> {
@@ -876,6 +1297,293 @@ impl<'db> InferCtx<'db> {
         Err(())
     }
 
+    fn unify_expr_var(&mut self, expr: ExprId, expected: TyVar, actual: TyVar) {
+        if self.try_unify_var(expected, actual).is_err() {
+            self.body_ctx
+                .type_mismatches
+                .push((expr.into(), expected, actual));
+        }
+    }
+
+    /// Checks a function body against its return annotation, the mismatch is reported at the
+    /// expression which gives the body its value.
+    fn unify_return_var(&mut self, body: ExprId, annotation: TyVar, actual: TyVar) {
+        let mut tail = body;
+        while let Expr::Block { stmts } = &self.body[tail] {
+            match stmts.last() {
+                Some(Statement::Expr { expr }) => tail = *expr,
+                _ => break,
+            }
+        }
+        self.unify_expr_var(tail, annotation, actual);
+    }
+
+    fn unify_annotation_var(&mut self, pattern: PatternId, annotation: TyVar, actual: TyVar) {
+        if self.try_unify_var(annotation, actual).is_err() {
+            self.body_ctx
+                .annotation_mismatches
+                .push((pattern, annotation, actual));
+        }
+    }
+
+    fn unify_pattern_var(&mut self, pattern: PatternId, expected: TyVar, actual: TyVar) {
+        if self.try_unify_var(expected, actual).is_err() {
+            self.body_ctx
+                .type_mismatches
+                .push((pattern.into(), expected, actual));
+        }
+    }
+
+    /// Infers the arguments of `call` and applies the callee to them. `piped` is the left side of
+    /// a pipe, passed as the first argument.
+    fn infer_call(
+        &mut self,
+        call: ExprId,
+        fun_ty: TyVar,
+        args: &[(Option<SmolStr>, ExprId)],
+        piped: Option<(ExprId, TyVar)>,
+    ) -> TyVar {
+        let mut all_args = Vec::new();
+        let mut arg_tys = Vec::new();
+        if let Some((arg, arg_ty)) = piped {
+            all_args.push((None, arg));
+            arg_tys.push((None, arg_ty));
+        }
+        let mut hole = None;
+        // add(_, 1) => fn(_hole) { add(_hole, 1) }
+        for (label, arg) in args {
+            let var = self.infer_expr(*arg);
+            if let Expr::Hole = self.body[*arg] {
+                hole = Some(var);
+            }
+            all_args.push((label.clone(), *arg));
+            arg_tys.push((label.clone(), var));
+        }
+
+        let ret_ty = self.apply(call, fun_ty, &all_args, &arg_tys, None);
+
+        if let Some(hole) = hole {
+            return Ty::Function {
+                params: vec![(None, hole)],
+                return_: ret_ty,
+            }
+            .intern(self);
+        }
+        ret_ty
+    }
+
+    /// Applies the callee of `call` to its arguments and returns the type of the result. A callee
+    /// which isn't known to be a function yet becomes one taking the arguments.
+    fn apply(
+        &mut self,
+        call: ExprId,
+        fun_ty: TyVar,
+        args: &[(Option<SmolStr>, ExprId)],
+        arg_tys: &[(Option<SmolStr>, TyVar)],
+        trailing: Option<TyVar>,
+    ) -> TyVar {
+        if let Ty::Function { params, return_ } = self.table.get_mut(fun_ty.0).clone() {
+            self.check_call_args(call, &params, args, arg_tys, trailing);
+            return return_;
+        }
+        let ret_ty = self.new_ty_var();
+        let mut params = arg_tys.to_vec();
+        params.extend(trailing.map(|ty| (None, ty)));
+        let expected = Ty::Function {
+            params,
+            return_: ret_ty,
+        }
+        .intern(self);
+        self.unify_expr_var(call, expected, fun_ty);
+        ret_ty
+    }
+
+    /// Matches the arguments of `call` up with the parameters of the callee. Every argument is
+    /// unified with its parameter, so mismatches are reported at the argument instead of at the
+    /// call, and arguments that don't fit are reported.
+    ///
+    /// `trailing` is an implicit last argument, like the callback of a `use`, its mismatches are
+    /// reported at the call.
+    fn check_call_args(
+        &mut self,
+        call: ExprId,
+        params: &[(Option<SmolStr>, TyVar)],
+        args: &[(Option<SmolStr>, ExprId)],
+        arg_tys: &[(Option<SmolStr>, TyVar)],
+        trailing: Option<TyVar>,
+    ) {
+        let found = args.len() + usize::from(trailing.is_some());
+        if found != params.len() {
+            let kind = CallErrorKind::WrongArity {
+                expected: params.len(),
+                found,
+            };
+            self.push_call_error(call, call, kind);
+        }
+
+        // Labelled arguments take the parameter with their label, positional ones fill the rest
+        // in order.
+        let mut slots = vec![None; params.len()];
+        let mut positional = Vec::new();
+        let mut seen_labelled = false;
+        for ((label, arg), (_, arg_ty)) in args.iter().zip(arg_tys) {
+            let Some(label) = label else {
+                if seen_labelled {
+                    self.push_call_error(*arg, call, CallErrorKind::PositionalAfterLabelled);
+                }
+                positional.push((*arg, *arg_ty));
+                continue;
+            };
+            seen_labelled = true;
+            let idx = params
+                .iter()
+                .position(|(param_label, _)| param_label.as_ref() == Some(label));
+            match idx {
+                Some(idx) if slots[idx].is_none() => {
+                    self.unify_expr_var(*arg, params[idx].1, *arg_ty);
+                    slots[idx] = Some(*arg_ty);
+                }
+                Some(_) => {
+                    let kind = CallErrorKind::DuplicateLabel(label.clone());
+                    self.push_call_error(*arg, call, kind);
+                }
+                None => {
+                    let kind = CallErrorKind::UnknownLabel(label.clone());
+                    self.push_call_error(*arg, call, kind);
+                }
+            }
+        }
+        let mut free = (0..params.len())
+            .filter(|idx| slots[*idx].is_none())
+            .collect::<Vec<_>>()
+            .into_iter();
+        if let Some(trailing) = trailing {
+            if let Some(idx) = free.next_back() {
+                self.unify_expr_var(call, params[idx].1, trailing);
+            }
+        }
+        for ((arg, arg_ty), idx) in positional.into_iter().zip(free) {
+            self.unify_expr_var(arg, params[idx].1, arg_ty);
+        }
+    }
+
+    fn push_call_error(&mut self, source: ExprId, call: ExprId, kind: CallErrorKind) {
+        self.body_ctx
+            .call_errors
+            .push((source, CallError { call, kind }));
+    }
+
+    /// Quantifies over the unbound variables in the type of a `let` bound variable which were
+    /// introduced by its value and aren't shared with anything bound before, the variables below
+    /// `outer_vars`. Those may have been resolved to types containing other variables since, so
+    /// all variables reachable from them count as shared.
+    fn generalise(&mut self, pattern: PatternId, ty: TyVar, outer_vars: usize) {
+        let mut vars = Vec::new();
+        self.unbound_vars(ty, &mut vars, &mut HashSet::new());
+        if vars.is_empty() {
+            return;
+        }
+        // A single walk over the environment, everything it reaches is shared.
+        let mut outer = HashSet::new();
+        let mut outer_unbound = Vec::new();
+        for var in 0..outer_vars as u32 {
+            self.unbound_vars(TyVar(var), &mut outer_unbound, &mut outer);
+        }
+        vars.retain(|var| !outer.contains(var));
+        if !vars.is_empty() {
+            self.body_ctx.schemes.insert(pattern, vars);
+        }
+    }
+
+    fn unbound_vars(&mut self, ty: TyVar, vars: &mut Vec<u32>, seen: &mut HashSet<u32>) {
+        let root = self.table.find(ty.0);
+        // Without an occurs check types can be cyclic.
+        if !seen.insert(root) {
+            return;
+        }
+        match self.table.get_mut(root).clone() {
+            Ty::Unknown { .. } => vars.push(root),
+            Ty::Result { ok, err } => {
+                self.unbound_vars(ok, vars, seen);
+                self.unbound_vars(err, vars, seen);
+            }
+            Ty::List { of } => self.unbound_vars(of, vars, seen),
+            Ty::Adt {
+                generic_params: tys,
+                ..
+            }
+            | Ty::Tuple { fields: tys } => {
+                for ty in tys {
+                    self.unbound_vars(ty, vars, seen);
+                }
+            }
+            Ty::Function { params, return_ } => {
+                for (_, ty) in params {
+                    self.unbound_vars(ty, vars, seen);
+                }
+                self.unbound_vars(return_, vars, seen);
+            }
+            Ty::Nil
+            | Ty::Bool
+            | Ty::Int
+            | Ty::Float
+            | Ty::String
+            | Ty::BitArray
+            | Ty::UtfCodepoint => {}
+        }
+    }
+
+    /// Copies `ty`, replacing the quantified `vars` with fresh variables.
+    fn instantiate(&mut self, ty: TyVar, vars: &[u32], fresh: &mut HashMap<u32, TyVar>) -> TyVar {
+        let root = self.table.find(ty.0);
+        if let Some(var) = fresh.get(&root) {
+            return *var;
+        }
+        if vars.contains(&root) {
+            let var = self.new_ty_var();
+            fresh.insert(root, var);
+            return var;
+        }
+        // Cyclic types are left alone, they can't be quantified over anyway.
+        fresh.insert(root, ty);
+        let ty = match self.table.get_mut(root).clone() {
+            Ty::Result { ok, err } => Ty::Result {
+                ok: self.instantiate(ok, vars, fresh),
+                err: self.instantiate(err, vars, fresh),
+            },
+            Ty::List { of } => Ty::List {
+                of: self.instantiate(of, vars, fresh),
+            },
+            Ty::Adt {
+                adt_id,
+                generic_params,
+            } => Ty::Adt {
+                adt_id,
+                generic_params: generic_params
+                    .into_iter()
+                    .map(|ty| self.instantiate(ty, vars, fresh))
+                    .collect(),
+            },
+            Ty::Tuple { fields } => Ty::Tuple {
+                fields: fields
+                    .into_iter()
+                    .map(|ty| self.instantiate(ty, vars, fresh))
+                    .collect(),
+            },
+            Ty::Function { params, return_ } => Ty::Function {
+                params: params
+                    .into_iter()
+                    .map(|(label, ty)| (label, self.instantiate(ty, vars, fresh)))
+                    .collect(),
+                return_: self.instantiate(return_, vars, fresh),
+            },
+            _ => return ty,
+        };
+        let ty = ty.intern(self);
+        fresh.insert(root, ty);
+        ty
+    }
+
     fn infer_pattern(&mut self, pattern: PatternId, expected_ty_var: TyVar) -> TyVar {
         let pat_var = self.ty_for_pattern(pattern);
         match &self.body[pattern] {
@@ -885,16 +1593,34 @@ impl<'db> InferCtx<'db> {
                 fields,
             } => {
                 let (pat_ty, mut field_tys) = match module {
-                    None => self.resolve_variant(name),
-                    Some(module) => self
-                        .resolver
-                        .resolve_module(module)
-                        .and_then(|file| {
+                    None => self.resolve_variant(pattern.into(), name),
+                    Some(module) => match self.resolver.resolve_module(module) {
+                        Some(file) => {
                             let resolver = resolver_for_toplevel(self.db.upcast(), file);
-                            resolver.resolve_name(name)
-                        })
-                        .map(|res| self.variant_from_resolve_result(res))
-                        .unwrap_or_else(|| (self.new_ty_var(), Vec::new())),
+                            match resolver.resolve_name(name) {
+                                Some(res) => {
+                                    if let ResolveResult::Variant(it) = res {
+                                        self.check_opaque(pattern, it.parent());
+                                    }
+                                    self.variant_from_resolve_result(res)
+                                }
+                                None => {
+                                    self.unresolved(
+                                        pattern,
+                                        UnresolvedName::ModuleMember {
+                                            module: module.clone(),
+                                            name: name.clone(),
+                                        },
+                                    );
+                                    (self.new_ty_var(), Vec::new())
+                                }
+                            }
+                        }
+                        None => {
+                            self.unresolved(pattern, UnresolvedName::Module(module.clone()));
+                            (self.new_ty_var(), Vec::new())
+                        }
+                    },
                 };
 
                 while field_tys.len() < fields.len() {
@@ -927,7 +1653,7 @@ impl<'db> InferCtx<'db> {
                     }
                 }
 
-                self.unify_var(pat_ty, pat_var);
+                self.bind_var(pat_var, pat_ty);
             }
             Pattern::AlternativePattern { patterns } => {
                 for pat in patterns.iter() {
@@ -939,17 +1665,17 @@ impl<'db> InferCtx<'db> {
                 if let Some(as_name) = as_name {
                     self.infer_pattern(*as_name, expected_ty_var);
                 }
-                self.unify_var(pat_var, sub_pat);
+                self.bind_var(pat_var, sub_pat);
             }
             Pattern::Literal { kind } => {
                 match kind {
-                    LiteralKind::Int => self.unify_var_ty(pat_var, Ty::Int),
-                    LiteralKind::Float => self.unify_var_ty(pat_var, Ty::Float),
-                    LiteralKind::String => self.unify_var_ty(pat_var, Ty::String),
+                    LiteralKind::Int => self.bind_var_ty(pat_var, Ty::Int),
+                    LiteralKind::Float => self.bind_var_ty(pat_var, Ty::Float),
+                    LiteralKind::String => self.bind_var_ty(pat_var, Ty::String),
                 };
             }
             Pattern::Spread { name: _ } => {
-                self.unify_var_ty(
+                self.bind_var_ty(
                     pat_var,
                     Ty::List {
                         of: expected_ty_var,
@@ -964,35 +1690,108 @@ impl<'db> InferCtx<'db> {
                     self.infer_pattern(*field, new_ty);
                     field_ty.push(new_ty);
                 }
-                self.unify_var_ty(pat_var, Ty::Tuple { fields: field_ty });
+                self.bind_var_ty(pat_var, Ty::Tuple { fields: field_ty });
             }
             Pattern::List { elements } => {
                 let of = self.new_ty_var();
                 for elem in elements.iter() {
                     self.infer_pattern(*elem, of);
                 }
-                self.unify_var_ty(pat_var, Ty::List { of });
+                self.bind_var_ty(pat_var, Ty::List { of });
             }
             Pattern::Concat { pattern } => {
                 let expected_ty = self.new_ty_var();
-                self.unify_var_ty(expected_ty, Ty::String);
+                self.bind_var_ty(expected_ty, Ty::String);
                 self.infer_pattern(*pattern, expected_ty);
             }
+            Pattern::BitArray { segments } => {
+                for segment in segments.clone() {
+                    let expected = self.segment_ty(&segment.options, || {
+                        matches!(
+                            self.body[segment.value],
+                            Pattern::Literal {
+                                kind: LiteralKind::String
+                            }
+                        )
+                    });
+                    self.infer_pattern(segment.value, expected);
+                }
+                self.bind_var_ty(pat_var, Ty::BitArray);
+            }
             Pattern::Missing => {}
             Pattern::Hole => {}
-            Pattern::Variable { .. } => {}
+            Pattern::Variable { .. } => self.bind_var(pat_var, expected_ty_var),
         }
-        self.unify_var(expected_ty_var, pat_var);
+        self.unify_pattern_var(pattern, expected_ty_var, pat_var);
         pat_var
     }
 
-    fn resolve_variant(&mut self, name: &SmolStr) -> (TyVar, Vec<(Option<SmolStr>, TyVar)>) {
+    /// The type a bit array segment value must have, based on its options.
+    /// Checks any `size(..)` expressions along the way.
+    fn segment_ty(
+        &mut self,
+        options: &[BitArrayOption],
+        is_string_literal: impl FnOnce() -> bool,
+    ) -> TyVar {
+        let mut ty = None;
+        for option in options {
+            match option {
+                BitArrayOption::Int => ty = Some(Ty::Int),
+                BitArrayOption::Float => ty = Some(Ty::Float),
+                BitArrayOption::Bits | BitArrayOption::Bytes => ty = Some(Ty::BitArray),
+                BitArrayOption::Utf8 | BitArrayOption::Utf16 | BitArrayOption::Utf32 => {
+                    ty = Some(Ty::String)
+                }
+                BitArrayOption::Utf8Codepoint
+                | BitArrayOption::Utf16Codepoint
+                | BitArrayOption::Utf32Codepoint => ty = Some(Ty::UtfCodepoint),
+                BitArrayOption::Size(size) => {
+                    let size_ty = self.infer_expr(*size);
+                    let int = Ty::Int.intern(self);
+                    self.unify_expr_var(*size, int, size_ty);
+                }
+                _ => {}
+            }
+        }
+        // Segments without a type option default to `int`, except for string
+        // literals, which are implicitly `utf8`.
+        let ty = ty.unwrap_or_else(|| {
+            if is_string_literal() {
+                Ty::String
+            } else {
+                Ty::Int
+            }
+        });
+        ty.intern(self)
+    }
+
+    fn resolve_variant(
+        &mut self,
+        source: ExprOrPatternId,
+        name: &SmolStr,
+    ) -> (TyVar, Vec<(Option<SmolStr>, TyVar)>) {
         match self.resolver.resolve_name(name) {
             Some(res) => self.variant_from_resolve_result(res),
-            None => (self.new_ty_var(), Vec::new()),
+            None => {
+                self.unresolved(source, UnresolvedName::Constructor(name.clone()));
+                (self.new_ty_var(), Vec::new())
+            }
         }
     }
 
+    fn unresolved(&mut self, source: impl Into<ExprOrPatternId>, name: UnresolvedName) {
+        self.body_ctx.unresolved_names.push((source.into(), name));
+    }
+
+    /// Records a use of `adt`'s internals, returning whether they are hidden from us.
+    fn check_opaque(&mut self, source: impl Into<ExprOrPatternId>, adt: Adt) -> bool {
+        let hidden = adt.is_opaque_to(self.db.upcast(), Module { id: self.file_id });
+        if hidden {
+            self.body_ctx.opaque_uses.push((source.into(), adt));
+        }
+        hidden
+    }
+
     fn variant_from_resolve_result(
         &mut self,
         result: ResolveResult,
@@ -1029,6 +1828,21 @@ impl<'db> InferCtx<'db> {
         }
     }
 
+    fn type_from_constant(&mut self, constant: ModuleConstant) -> TyVar {
+        if self.skip_constants {
+            return self.new_ty_var();
+        }
+        let ty = constant.ty(self.db);
+        let file_id = constant.module(self.db.upcast()).id;
+        let resolver = mem::replace(
+            &mut self.resolver,
+            resolver_for_toplevel(self.db.upcast(), file_id),
+        );
+        let ty = self.make_type(ty, &mut HashMap::new());
+        self.resolver = resolver;
+        ty
+    }
+
     fn type_from_variant(
         &mut self,
         variant: hir::Variant,
@@ -1086,11 +1900,10 @@ impl<'db> InferCtx<'db> {
                     generic_params: params2,
                 },
             ) => {
-                // diagnostic if adt_id not the same!
                 if id1 != id2 {
-                    tracing::info!("Different adts {:?} {:?}", id1, id2);
+                    return Err(());
                 }
-                for (p1, p2) in params1.clone().into_iter().zip(params2.into_iter()) {
+                for (p1, p2) in params1.clone().into_iter().zip(params2) {
                     self.try_unify_var(p1, p2)?;
                 }
                 Ty::Adt {
@@ -1112,32 +1925,30 @@ impl<'db> InferCtx<'db> {
                     return_: ret2,
                 },
             ) => {
-                let mut params2_mut = params2.clone();
+                if params1.len() != params2.len() {
+                    return Err(());
+                }
+                let mut params2_mut = params2;
 
                 // Reorder labels
                 // if something needs to be changed here, it's very likely that infer_pattern -> VariantRef should be updated aswell
-                let params1_removed: Vec<_> = params1
-                    .iter()
-                    .filter(|(label1, ty1)| {
-                        if let Some((idx_second, _)) = params2_mut
-                            .iter()
-                            .find_position(|(label2, _)| *label1 == *label2)
-                        {
+                let mut params1_removed = Vec::new();
+                for (label1, ty1) in params1.iter() {
+                    match params2_mut
+                        .iter()
+                        .find_position(|(label2, _)| label1 == label2)
+                    {
+                        Some((idx_second, _)) => {
                             let (_, ty2) = params2_mut.remove(idx_second);
-                            let _ = self.try_unify_var(*ty1, ty2);
-
-                            false
-                        } else {
-                            true
+                            self.try_unify_var(*ty1, ty2)?;
                         }
-                    })
-                    .collect();
-
-                for (idx_first, (_, ty1)) in params1_removed.iter().enumerate() {
-                    if let Some((_, ty2)) = params2_mut.get(idx_first) {
-                        let _ = self.try_unify_var(*ty1, *ty2);
+                        None => params1_removed.push(*ty1),
                     }
                 }
+
+                for (ty1, (_, ty2)) in params1_removed.into_iter().zip(params2_mut) {
+                    self.try_unify_var(ty1, ty2)?;
+                }
                 self.try_unify_var(ret1, ret2)?;
                 Ty::Function {
                     params: params1,
@@ -1145,7 +1956,10 @@ impl<'db> InferCtx<'db> {
                 }
             }
             (Ty::Tuple { fields: fields1 }, Ty::Tuple { fields: fields2 }) => {
-                for (f1, f2) in fields1.clone().into_iter().zip(fields2.into_iter()) {
+                if fields1.len() != fields2.len() {
+                    return Err(());
+                }
+                for (f1, f2) in fields1.clone().into_iter().zip(fields2) {
                     self.try_unify_var(f1, f2)?;
                 }
                 Ty::Tuple { fields: fields1 }
@@ -1158,7 +1972,7 @@ impl<'db> InferCtx<'db> {
                 lhs
             }
         };
-        return Ok(ty);
+        Ok(ty)
     }
 }
 
@@ -1167,55 +1981,105 @@ fn finish_infer(
     body_ctx: HashMap<FunctionId, BodyCtx>,
     fn_ty: HashMap<FunctionId, TyVar>,
 ) -> HashMap<FunctionId, InferenceResult> {
-    let mut i = Collector::new(&mut table);
+    let aliases = body_ctx
+        .values()
+        .flat_map(|ctx| ctx.aliases.iter().map(|(ty, alias)| (*ty, alias.clone())));
+    let mut i = Collector::new(&mut table, aliases.collect());
 
     let mut inference_map = HashMap::new();
-
     for (fn_id, ctx) in body_ctx.into_iter() {
-        let mut pattern_ty_map = ArenaMap::new();
-        let mut expr_ty_map = ArenaMap::new();
+        let ty = *fn_ty.get(&fn_id).expect("Compiler error");
+        inference_map.insert(fn_id, collect_result(&mut i, ctx, ty));
+    }
+    inference_map
+}
 
-        for (pattern, ty) in ctx.pattern_to_ty.iter() {
-            pattern_ty_map.insert(pattern, i.collect(*ty));
-        }
+fn collect_result(i: &mut Collector<'_>, ctx: BodyCtx, ty: TyVar) -> InferenceResult {
+    let mut pattern_ty_map = ArenaMap::new();
+    let mut expr_ty_map = ArenaMap::new();
 
-        for (expr, ty) in ctx.expr_to_ty.iter() {
-            expr_ty_map.insert(expr, i.collect(*ty));
-        }
+    for (pattern, ty) in ctx.pattern_to_ty.iter() {
+        pattern_ty_map.insert(pattern, i.collect(*ty));
+    }
 
-        inference_map.insert(
-            fn_id,
-            InferenceResult {
-                fn_ty: i.collect(*fn_ty.get(&fn_id).expect("Compiler error")),
-                module_resolution: ctx.module_resolution,
-                field_resolution: ctx.field_resolution,
-                pattern_ty_map,
-                expr_ty_map,
-            },
-        );
+    for (expr, ty) in ctx.expr_to_ty.iter() {
+        expr_ty_map.insert(expr, i.collect(*ty));
     }
 
-    inference_map
+    let type_mismatches = ctx
+        .type_mismatches
+        .iter()
+        .map(|(source, expected, actual)| {
+            let mismatch = TypeMismatch {
+                expected: i.collect(*expected),
+                actual: i.collect(*actual),
+            };
+            (*source, mismatch)
+        })
+        .collect();
+    let annotation_mismatches = ctx
+        .annotation_mismatches
+        .iter()
+        .map(|(pattern, annotation, actual)| {
+            let mismatch = TypeMismatch {
+                expected: i.collect(*annotation),
+                actual: i.collect(*actual),
+            };
+            (*pattern, mismatch)
+        })
+        .collect();
+
+    InferenceResult {
+        fn_ty: i.collect(ty),
+        module_resolution: ctx.module_resolution,
+        field_resolution: ctx.field_resolution,
+        pattern_ty_map,
+        expr_ty_map,
+        type_mismatches,
+        annotation_mismatches,
+        unresolved_names: ctx.unresolved_names,
+        opaque_uses: ctx.opaque_uses,
+        call_errors: ctx.call_errors,
+    }
 }
+
 /// Traverse the table and freeze all `Ty`s into immutable ones.
 struct Collector<'a> {
     cache: Vec<Option<super::Ty>>,
     table: &'a mut UnionFind<Ty>,
     env: HashMap<u32, SmolStr>,
     uid: u32,
+    aliases: HashMap<TyVar, (TypeAliasId, Vec<TyVar>)>,
 }
 
 impl<'a> Collector<'a> {
-    fn new(table: &'a mut UnionFind<Ty>) -> Self {
+    fn new(
+        table: &'a mut UnionFind<Ty>,
+        aliases: HashMap<TyVar, (TypeAliasId, Vec<TyVar>)>,
+    ) -> Self {
         Self {
             cache: vec![None; table.len()],
             table,
             env: HashMap::new(),
             uid: 0,
+            aliases,
         }
     }
 
     fn collect(&mut self, ty: TyVar) -> super::Ty {
+        // Aliases belong to the variable, the cache to the type all unified variables share.
+        let ret = self.collect_root(ty);
+        match self.aliases.get(&ty).cloned() {
+            Some((alias_id, params)) => super::Ty::Alias {
+                alias_id,
+                params: Arc::new(params.into_iter().map(|p| self.collect(p)).collect()),
+                ty: Arc::new(ret),
+            },
+            None => ret,
+        }
+    }
+
+    fn collect_root(&mut self, ty: TyVar) -> super::Ty {
         let i = self.table.find(ty.0);
         if let Some(ty) = self.cache[i as usize].clone() {
             return ty;
@@ -1250,6 +2114,7 @@ impl<'a> Collector<'a> {
             Ty::Float => super::Ty::Float,
             Ty::String => super::Ty::String,
             Ty::BitArray => super::Ty::BitArray,
+            Ty::UtfCodepoint => super::Ty::UtfCodepoint,
             Ty::Result { ok, err } => {
                 let ok = self.collect(*ok);
                 let err = self.collect(*err);
@@ -1265,7 +2130,7 @@ impl<'a> Collector<'a> {
             Ty::Function { params, return_ } => {
                 let mut super_params = Vec::new();
                 for (label, param) in params {
-                    super_params.push((label.clone(), self.collect(param.clone())));
+                    super_params.push((label.clone(), self.collect(*param)));
                 }
                 let super_return = self.collect(*return_);
                 super::Ty::Function {
@@ -1293,7 +2158,7 @@ impl<'a> Collector<'a> {
 
                 super::Ty::Adt {
                     // try to use module from adt
-                    adt_id: adt_id.clone(),
+                    adt_id: *adt_id,
                     params: Arc::new(params),
                 }
             }
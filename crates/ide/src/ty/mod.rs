@@ -1,4 +1,5 @@
 pub mod display;
+mod exhaustiveness;
 mod infer;
 mod union_find;
 
@@ -7,11 +8,13 @@ mod tests;
 
 use std::{collections::HashMap, sync::Arc};
 
-pub use infer::{FieldResolution, InferenceResult};
+pub use exhaustiveness::PatternDiagnostic;
+pub(crate) use exhaustiveness::{check_patterns, missing_patterns, MAX_MISSING};
+pub use infer::{CallErrorKind, FieldResolution, InferenceResult, UnresolvedName};
 use smol_str::SmolStr;
 
 use crate::{
-    def::hir_def::{AdtId, FunctionId},
+    def::hir_def::{AdtId, ConstId, FunctionId, TypeAliasId},
     ide::Upcast,
     DefDatabase,
 };
@@ -19,10 +22,19 @@ use crate::{
 #[salsa::query_group(TyDatabaseStorage)]
 pub trait TyDatabase: DefDatabase + Upcast<dyn DefDatabase> {
     #[salsa::invoke(infer::infer_function_query)]
+    #[salsa::cycle(infer::infer_function_recover)]
     fn infer_function(&self, fn_id: FunctionId) -> Arc<InferenceResult>;
 
     #[salsa::invoke(infer::infer_function_group_query)]
+    #[salsa::cycle(infer::infer_function_group_recover)]
     fn infer_function_group(&self, group: Vec<FunctionId>) -> HashMap<FunctionId, InferenceResult>;
+
+    #[salsa::invoke(infer::function_signature_query)]
+    fn function_signature(&self, fn_id: FunctionId) -> Option<Ty>;
+
+    #[salsa::invoke(infer::infer_const_query)]
+    #[salsa::cycle(infer::infer_const_recover)]
+    fn infer_const(&self, const_id: ConstId) -> Arc<InferenceResult>;
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -38,6 +50,7 @@ pub enum Ty {
     Float,
     String,
     BitArray,
+    UtfCodepoint,
     Result {
         ok: Arc<Ty>,
         err: Arc<Ty>,
@@ -58,4 +71,20 @@ pub enum Ty {
     Tuple {
         fields: Arc<Vec<Ty>>,
     },
+    /// A type written through an alias, `ty` is what it expands to.
+    Alias {
+        alias_id: TypeAliasId,
+        params: Arc<Vec<Ty>>,
+        ty: Arc<Ty>,
+    },
+}
+
+impl Ty {
+    /// The type behind any aliases, which is what matters for its structure.
+    pub fn unalias(&self) -> &Ty {
+        match self {
+            Ty::Alias { ty, .. } => ty.unalias(),
+            ty => ty,
+        }
+    }
 }
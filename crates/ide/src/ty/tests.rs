@@ -21,7 +21,11 @@ fn check_fn(src: &str, expect: Expect) {
                 output.push(format!("{}: {}", func.name, infer.fn_ty.display(&db)));
             }
             crate::def::hir_def::ModuleDefId::AdtId(_) => {}
-            crate::def::hir_def::ModuleDefId::ModuleConstant(_) => {}
+            crate::def::hir_def::ModuleDefId::ModuleConstant(id) => {
+                let constant = crate::def::hir::ModuleConstant { id };
+                let ty = constant.ty(&db);
+                output.push(format!("{}: {}", constant.name(&db), ty.display(&db)));
+            }
             crate::def::hir_def::ModuleDefId::VariantId(_) => {}
             crate::def::hir_def::ModuleDefId::TypeAliasId(_) => {}
         }
@@ -82,7 +86,11 @@ fn check_fix(src: &str, expect: Expect) {
                 output.push(format!("{}: {}", func.name, infer.fn_ty.display(&db)));
             }
             crate::def::hir_def::ModuleDefId::AdtId(_) => {}
-            crate::def::hir_def::ModuleDefId::ModuleConstant(_) => {}
+            crate::def::hir_def::ModuleDefId::ModuleConstant(id) => {
+                let constant = crate::def::hir::ModuleConstant { id };
+                let ty = constant.ty(&db);
+                output.push(format!("{}: {}", constant.name(&db), ty.display(&db)));
+            }
             crate::def::hir_def::ModuleDefId::VariantId(_) => {}
             crate::def::hir_def::ModuleDefId::TypeAliasId(_) => {}
         }
@@ -306,8 +314,7 @@ fn adt_resolve() {
 fn pipe() {
     check_fn(
         "fn main(a) { 1 |> a(2) } ",
-        expect![[r#"
-        main: fn(fn(Int) -> fn(Int) -> a) -> a"#]],
+        expect!["main: fn(fn(Int, Int) -> a) -> a"],
     )
 }
 
@@ -315,16 +322,36 @@ fn pipe() {
 fn pipe_sugar() {
     check_fn(
         "fn main(a) { 1 |> a(2) } ",
-        expect![[r#"
-        main: fn(fn(Int) -> fn(Int) -> a) -> a"#]],
+        expect!["main: fn(fn(Int, Int) -> a) -> a"],
     );
     check_fn(
         "fn main() { 1 |> a(2) } ",
         expect![[r#"
         main: fn() -> a"#]],
+    );
+    check_fn(
+        "fn main(f) { 1 |> f(\"a\") } ",
+        expect!["main: fn(fn(Int, String) -> a) -> a"],
     )
 }
 
+#[test]
+fn bool_and_unary_ops() {
+    check_fn(
+        "fn and(a, b) { a && !b }
+        fn or(a, b) { a != b || False }
+        fn neg(a) { -a }
+        fn neg_float(a) { -.a }
+        fn neg_literal() { -1.5 }",
+        expect![[r#"
+            and: fn(Bool, Bool) -> Bool
+            or: fn(a, a) -> Bool
+            neg: fn(Int) -> Int
+            neg_float: fn(Float) -> Float
+            neg_literal: fn() -> Float"#]],
+    );
+}
+
 #[test]
 fn use_() {
     check_fn(
@@ -337,6 +364,57 @@ fn use_() {
     );
 }
 
+#[test]
+fn use_annotated() {
+    check_all(
+        "fn apply(f) { f(1, \"a\") }
+        fn main() {
+            use a: Int, b: String <- apply
+            b
+        }",
+        expect![[r#"
+            apply: fn(fn(Int, String) -> a) -> a
+              f: fn(Int, String) -> a
+            main: fn() -> String
+              a: Int
+              b: String"#]],
+    );
+}
+
+#[test]
+fn use_labelled_callback() {
+    check_all(
+        "fn guard(when cond: Bool, return value: a, otherwise cb: fn() -> a) -> a { value }
+        fn main() {
+            use <- guard(return: \"early\", when: True)
+            \"late\"
+        }",
+        expect![[r#"
+            guard: fn(Bool, a, fn() -> a) -> a
+              cond: Bool
+              value: a
+              cb: fn() -> a
+            main: fn() -> String"#]],
+    );
+}
+
+#[test]
+fn use_bound_field_access() {
+    check_all(
+        "type User { User(name: String) }
+        fn with_user(f: fn(User) -> a) -> a { f(User(\"bob\")) }
+        fn main() {
+            use user <- with_user()
+            user.name
+        }",
+        expect![[r#"
+            with_user: fn(fn(User) -> a) -> a
+              f: fn(User) -> a
+            main: fn() -> String
+              user: User"#]],
+    );
+}
+
 #[test]
 fn record_spread() {
     check_fn(
@@ -350,6 +428,120 @@ fn record_spread() {
     )
 }
 
+#[test]
+fn record_update() {
+    check_fn(
+        "type Pet { Dog(name: String, age: Int) }
+      fn main(pet, n) {
+        Dog(..pet, name: n)
+      }",
+        expect!["main: fn(Pet, String) -> Pet"],
+    )
+}
+
+#[test]
+fn parameterised_alias() {
+    check_fn(
+        "type Handler(a) = fn(String) -> Result(a, Nil)
+      type Pair(a) = #(a, a)
+      fn run(h: Handler(Int)) { h(\"request\") }
+      fn main() { run(fn(_) { Ok(1) }) }
+      fn swap(p: Pair(b), x: b) { #(p.1, x) }",
+        expect![[r#"
+            run: fn(fn(String) -> Result(Int, Nil)) -> Result(Int, Nil)
+            main: fn() -> Result(Int, Nil)
+            swap: fn(#(a, a), a) -> #(a, a)"#]],
+    )
+}
+
+#[test]
+fn cyclic_alias() {
+    check_fn(
+        "type A = List(B)
+      type B = A
+      fn main(a: A) { a }",
+        expect!["main: fn(List(a)) -> List(a)"],
+    )
+}
+
+#[test]
+fn module_constants() {
+    check_fn(
+        "type Pet { Dog(name: String, age: Int) }
+      const names = [\"a\", \"b\"]
+      const pair: #(Int, List(String)) = #(1, names)
+      const dog = Dog(name: \"Rex\", age: 3)
+      const empty = []
+      fn main() { #(pair, dog, empty) }",
+        expect![[r#"
+            main: fn() -> #(#(Int, List(String)), Pet, List(a))
+            names: List(String)
+            pair: #(Int, List(String))
+            dog: Pet
+            empty: List(a)"#]],
+    )
+}
+
+#[test]
+fn constant_through_function_cycle() {
+    check_all(
+        "const e = f
+      fn f() {
+        let x = e
+        x
+      }",
+        expect![[r#"
+            f: fn() -> b
+              x: a"#]],
+    )
+}
+
+#[test]
+fn qualified_module_constant() {
+    check_fix(
+        "#- main.gleam
+import config
+fn main() { $0config.port }
+#- config.gleam
+pub const port = 8080",
+        expect!["main: fn() -> Int"],
+    )
+}
+
+#[test]
+fn external_functions() {
+    check_fn(
+        r#"@external(erlang, "lists", "reverse")
+@external(javascript, "./ffi.mjs", "reverse")
+pub fn reverse(list: List(a)) -> List(a)
+@external(erlang, "ffi", "length")
+pub fn length(list: List(a)) -> Int { 0 }
+fn main() { #(reverse([1]), length([])) }"#,
+        expect![[r#"
+            reverse: fn(List(a)) -> List(a)
+            length: fn(List(a)) -> Int
+            main: fn() -> #(List(Int), Int)"#]],
+    )
+}
+
+#[test]
+fn opaque_type() {
+    check_fix(
+        "#- main.gleam
+import lib
+fn main(t: lib.Token) { $0t.secret }
+#- lib.gleam
+pub opaque type Token { Token(secret: Int) }
+pub fn peek(t: Token) { t.secret }",
+        expect!["main: fn(Token) -> a"],
+    );
+    check_fn(
+        "pub opaque type Token { Token(secret: Int) }
+fn peek(t: Token) { t.secret }",
+        expect!["peek: fn(Token) -> Int"],
+    )
+}
+
 #[test]
 fn use_pattern() {
     check_fn(
@@ -837,6 +1029,27 @@ fn index() {
     )
 }
 
+#[test]
+fn bit_array_segments() {
+    check_all(
+        r#"
+fn parse(bits) {
+    let <<len:8, body:bytes-size(len), f:float, c:utf8_codepoint, rest:bits>> = bits
+    #(len, body, f, c, rest, <<"abc", 1.0:float, body:bits>>)
+}
+  "#,
+        expect![[r#"
+            parse: fn(BitArray) -> #(Int, BitArray, Float, UtfCodepoint, BitArray, BitArray)
+              bits: BitArray
+              len: Int
+              body: BitArray
+              f: Float
+              c: UtfCodepoint
+              rest: BitArray
+              <<len:8, body:bytes-size(len), f:float, c:utf8_codepoint, rest:bits>>: BitArray"#]],
+    )
+}
+
 #[test]
 fn infer_labelled_pipe() {
     check_fn(
@@ -884,3 +1097,124 @@ fn infer_annotated_let() {
         expect![r#"bobo: fn(String) -> String"#],
     )
 }
+
+#[test]
+fn labelled_call_args() {
+    check_fn(
+        r#"fn wrap(value v: a, in l: List(b)) { #(v, l) }
+fn main() { wrap(in: ["a"], value: 1) }
+fn partial() { wrap(1, in: _) }"#,
+        expect![[r#"
+            wrap: fn(a, List(b)) -> #(a, List(b))
+            main: fn() -> #(Int, List(String))
+            partial: fn() -> fn(List(a)) -> #(Int, List(a))"#]],
+    )
+}
+
+#[test]
+fn pipe_semantics() {
+    check_fn(
+        r#"fn add(a: Int, b: Int) -> Int { a + b }
+fn show(a: Int) -> String { todo }
+fn adder(a: Int) -> fn(Int) -> Int { fn(b) { a + b } }
+fn wrap(value v: a, in l: List(a)) -> List(a) { [v, ..l] }
+fn pipeline(x) { x |> add(1) |> add(2) |> show }
+fn call_result() { 1 |> adder(2) }
+fn capture(x) { x |> add(1, _) |> wrap(_, []) }
+fn labelled_hole(x) { [1.5] |> wrap(in: _, value: x) }
+fn labelled_insert(x) { x |> wrap(in: ["a"]) }"#,
+        expect![[r#"
+            add: fn(Int, Int) -> Int
+            show: fn(Int) -> String
+            adder: fn(Int) -> fn(Int) -> Int
+            wrap: fn(a, List(a)) -> List(a)
+            pipeline: fn(Int) -> String
+            call_result: fn() -> Int
+            capture: fn(Int) -> List(Int)
+            labelled_hole: fn(Float) -> List(Float)
+            labelled_insert: fn(String) -> List(String)"#]],
+    )
+}
+
+#[test]
+fn let_polymorphism() {
+    check_fn(
+        r#"fn id(x) { x }
+fn lambda() { let f = fn(x) { x } #(f(1), f("a")) }
+fn reference() { let f = id #(f(1), f("a")) }
+fn empty() { let xs = [] #([1, ..xs], ["a", ..xs]) }
+fn captured(x) { let pair = fn(y) { #(x, y) } #(pair(1), pair("a")) }
+fn applied(f) { let r = f(1) r + 1 }"#,
+        expect![[r#"
+            id: fn(a) -> a
+            lambda: fn() -> #(Int, String)
+            reference: fn() -> #(Int, String)
+            empty: fn() -> #(List(Int), List(String))
+            captured: fn(a) -> #(#(a, Int), #(a, String))
+            applied: fn(fn(Int) -> Int) -> Int"#]],
+    )
+}
+
+#[test]
+fn generic_instantiation() {
+    check_fix(
+        r#"
+#- main.gleam
+import list
+type Box(a) { Box(a) }
+fn boxes() { #(Box(1), Box("a")) }
+fn maps() { #(list.map([1], fn(x) { "a" }), list.map(["a"], fn(x) { 1.0 }))$0 }
+#- list.gleam
+pub fn map(l: List(a), f: fn(a) -> b) -> List(b) { todo }
+"#,
+        expect![[r#"
+            boxes: fn() -> #(Box(Int), Box(String))
+            maps: fn() -> #(List(String), List(Float))"#]],
+    )
+}
+
+#[test]
+fn let_annotation() {
+    check_fn(
+        r#"fn empty() { let xs: List(Int) = [] xs }
+fn generic() { let f: fn(a) -> a = fn(x) { x } #(f(1), f("a")) }
+fn mismatch() { let x: String = 1 x }"#,
+        expect![[r#"
+            empty: fn() -> List(Int)
+            generic: fn() -> #(Int, String)
+            mismatch: fn() -> String"#]],
+    )
+}
+
+#[test]
+fn declared_signature() {
+    check_fix(
+        r#"
+#- main.gleam
+import lib
+fn main() { #(lib.broken(1), lib.partial(1), lib.unparsed)$0 }
+#- lib.gleam
+pub fn broken(x: Int) -> String { x }
+pub fn partial(x: Int) { x }
+pub fn unparsed(f: fn(Int)) -> Int { f(1) }
+"#,
+        expect!["main: fn() -> #(String, Int, fn(fn(Int) -> Int) -> Int)"],
+    )
+}
+
+#[test]
+fn alternative_pattern_rows() {
+    check_all(
+        r#"fn main(a, b) { case a, b { 1, "x" | 2, "y" -> a  _, _ -> a } }"#,
+        expect![[r#"
+            main: fn(Int, String) -> Int
+              a: Int
+              b: String
+              1: Int
+              "x": String
+              2: Int
+              "y": String
+              _: Int
+              _: String"#]],
+    );
+}
@@ -27,6 +27,11 @@ pub enum BinaryOpKind {
     FloatGTE,
     FloatLTE,
     Eq,
+    NotEq,
+
+    //Bool
+    And,
+    Or,
 
     //String
     Concat,
@@ -36,6 +41,7 @@ pub enum BinaryOpKind {
 pub enum UnaryOpKind {
     Not,
     Negate,
+    FloatNegate,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -197,6 +203,8 @@ enums! {
         Lambda,
         BinaryOp,
         Hole,
+        Todo,
+        Panic,
         Tuple,
         Pipe,
         UnaryOp,
@@ -224,6 +232,8 @@ enums! {
         PatternSpread,
         AsPattern,
         PatternConcat,
+        PatternBitArray,
+        AlternativePattern,
     },
     TypeNameOrName {
         Name,
@@ -283,7 +293,29 @@ asts! {
         expressions: [StatementExpr],
     },
     BIT_ARRAY = BitArray {
+        segments: [BitArraySegment],
+    },
+    PATTERN_BIT_ARRAY = PatternBitArray {
+        segments: [BitArraySegment],
+    },
+    BIT_ARRAY_SEGMENT = BitArraySegment {
+        value: Expr,
+        pattern: Pattern,
+        options: [BitArrayOption],
+    },
+    BIT_ARRAY_OPTION = BitArrayOption {
+        arg: Expr,
+
+        pub fn name_token(&self) -> Option<SyntaxToken> {
+            self.0
+                .children_with_tokens()
+                .filter_map(NodeOrToken::into_token)
+                .find(|it| it.kind() == IDENT)
+        }
 
+        pub fn name(&self) -> Option<SmolStr> {
+            self.name_token().map(|t| t.text().into())
+        }
     },
     BINARY_OP = BinaryOp {
         lhs: Expr,
@@ -310,8 +342,11 @@ asts! {
                     T![">."] => BinaryOpKind::FloatGT,
                     T!["<."] => BinaryOpKind::FloatLT,
                     T![">=."] => BinaryOpKind::FloatGTE,
-                    T!["<=."] => BinaryOpKind::FloatGTE,
+                    T!["<=."] => BinaryOpKind::FloatLTE,
                     T!["=="] => BinaryOpKind::Eq,
+                    T!["!="] => BinaryOpKind::NotEq,
+                    T!["&&"] => BinaryOpKind::And,
+                    T!["||"] => BinaryOpKind::Or,
                     T!["<>"] => BinaryOpKind::Concat,
                     _ => return None,
                 };
@@ -354,10 +389,15 @@ asts! {
         name: TypeName,
         constructors: [Variant],
         generic_params: GenericParamList,
+        attributes: [Attribute],
 
         pub fn is_public(&self) -> bool {
             self.syntax().children_with_tokens().any(|it| it.kind() == T!["pub"])
         }
+
+        pub fn is_opaque(&self) -> bool {
+            self.syntax().children_with_tokens().any(|it| it.kind() == T!["opaque"])
+        }
     },
     GENERIC_PARAM_LIST = GenericParamList {
         params: [TypeExpr],
@@ -366,6 +406,7 @@ asts! {
         name: TypeName,
         type_: TypeExpr,
         generic_params: GenericParamList,
+        attributes: [Attribute],
 
         pub fn is_public(&self) -> bool {
             self.syntax().children_with_tokens().any(|it| it.kind() == T!["pub"])
@@ -399,11 +440,40 @@ asts! {
         param_list: ParamList,
         return_type: TypeExpr,
         body: Block,
+        externals: [ExternalAttr],
+        attributes: [Attribute],
 
         pub fn is_public(&self) -> bool {
             self.syntax().children_with_tokens().any(|it| it.kind() == T!["pub"])
         }
     },
+    ATTRIBUTE = Attribute {
+        pub fn name(&self) -> Option<SyntaxToken> {
+            self.0.children_with_tokens().filter_map(|it| it.into_token()).find(|it| it.kind() == IDENT)
+        }
+
+        /// The literal arguments, `@target(erlang)` has `erlang`.
+        pub fn args(&self) -> impl Iterator<Item = SyntaxToken> {
+            self.0
+                .children_with_tokens()
+                .filter_map(|it| it.into_token())
+                .skip_while(|it| it.kind() != T!["("])
+                .filter(|it| matches!(it.kind(), IDENT | STRING | INTEGER | FLOAT))
+        }
+    },
+    EXTERNAL_ATTR = ExternalAttr {
+        pub fn target(&self) -> Option<SyntaxToken> {
+            self.0.children_with_tokens().filter_map(|it| it.into_token()).find(|it| it.kind() == IDENT)
+        }
+
+        pub fn module(&self) -> Option<SyntaxToken> {
+            self.0.children_with_tokens().filter_map(|it| it.into_token()).filter(|it| it.kind() == STRING).nth(0)
+        }
+
+        pub fn function(&self) -> Option<SyntaxToken> {
+            self.0.children_with_tokens().filter_map(|it| it.into_token()).filter(|it| it.kind() == STRING).nth(1)
+        }
+    },
     EXPR_CALL = ExprCall {
         func: Expr,
         arguments: ArgList,
@@ -454,8 +524,9 @@ asts! {
     },
     MODULE_CONSTANT = ModuleConstant [HasDocParts] {
         name: Name,
-        value: ConstantExpr,
+        value: Expr,
         annotation: TypeExpr,
+        attributes: [Attribute],
         pub fn is_public(&self) -> bool {
             self.syntax().children_with_tokens().any(|it| it.kind() == T!["pub"])
         }
@@ -522,6 +593,7 @@ asts! {
                 let kind = match tok.kind() {
                     T!["!"] => UnaryOpKind::Not,
                     T!["-"] => UnaryOpKind::Negate,
+                    T!["-."] => UnaryOpKind::FloatNegate,
                     _ => return None,
                 };
                 Some((tok, kind))
@@ -534,6 +606,12 @@ asts! {
             self.op_details().map(|t| t.1)
         }
     },
+    TODO = Todo {
+        message: Expr,
+    },
+    PANIC = Panic {
+        message: Expr,
+    },
     HOLE = Hole {
         pub fn token(&self) -> Option<SyntaxToken> {
             self.0.children_with_tokens().find_map(NodeOrToken::into_token)
@@ -549,6 +627,10 @@ asts! {
         pattern: Pattern,
         annotation: TypeExpr,
         body: Expr,
+
+        pub fn is_assert(&self) -> bool {
+            self.syntax().children_with_tokens().any(|it| it.kind() == T!["assert"])
+        }
     },
     STMT_USE = StmtUse {
         assignments: [UseAssignment],
@@ -600,6 +682,7 @@ asts! {
     },
     CLAUSE = Clause {
         patterns: [AlternativePattern],
+        guard: PatternGuard,
         body: Expr,
     },
     AS_PATTERN = AsPattern {
@@ -651,7 +734,7 @@ pub trait HasDocParts: AstNode<Language = GleamLanguage> {
     fn doc_text(&self) -> String {
         self.doc_parts()
             .filter_map(|docpart| {
-                let slashes = number_of_slashes(&docpart).clone();
+                let slashes = number_of_slashes(&docpart);
                 Some(docpart.text()[slashes?..].to_string())
             })
             .join("\n")
@@ -691,10 +774,6 @@ mod tests {
     use super::*;
     use crate::tests::parse;
 
-    trait HasSyntaxNode {
-        fn has_syntax_node(&self) -> &SyntaxNode;
-    }
-
     trait AstTest {
         fn should_eq(&self, expect: &str);
     }
@@ -771,13 +850,11 @@ mod tests {
 
     #[test]
     fn pattern_spread() {
-        let e = parse::<Pattern>("fn spread() { case [] { [..name] -> name } }");
-        e.syntax().should_eq("[..name]");
-        match e {
-            Pattern::PatternList(list) => match list.elements().next().unwrap() {
-                Pattern::PatternSpread(spread) => spread.name().unwrap().syntax().should_eq("name"),
-                _ => unreachable!(),
-            },
+        // Clause patterns are wrapped in an alternative pattern, so look for the list itself.
+        let list = parse::<PatternList>("fn spread() { case [] { [..name] -> name } }");
+        list.syntax().should_eq("[..name]");
+        match list.elements().next().unwrap() {
+            Pattern::PatternSpread(spread) => spread.name().unwrap().syntax().should_eq("name"),
             _ => unreachable!(),
         }
     }
@@ -896,6 +973,38 @@ mod tests {
         assert!(params.next().is_none())
     }
 
+    #[test]
+    fn function_externals() {
+        let e = parse::<Function>(
+            "@external(erlang, \"lists\", \"reverse\")\n@external(javascript, \"./ffi.mjs\", \"reverse\")\nfn reverse(a: List(a)) -> List(a)",
+        );
+        let mut externals = e.externals();
+        let erl = externals.next().unwrap();
+        erl.target().unwrap().should_eq("erlang");
+        erl.module().unwrap().should_eq("\"lists\"");
+        erl.function().unwrap().should_eq("\"reverse\"");
+        let js = externals.next().unwrap();
+        js.target().unwrap().should_eq("javascript");
+        js.module().unwrap().should_eq("\"./ffi.mjs\"");
+        assert!(externals.next().is_none());
+        assert!(e.body().is_none());
+    }
+
+    #[test]
+    fn attributes() {
+        let e = parse::<ModuleConstant>("@deprecated(\"Too big\")\n@internal\nconst limit = 100");
+        let mut attrs = e.attributes();
+        let deprecated = attrs.next().unwrap();
+        deprecated.name().unwrap().should_eq("deprecated");
+        let mut args = deprecated.args();
+        args.next().unwrap().should_eq("\"Too big\"");
+        assert!(args.next().is_none());
+        let internal = attrs.next().unwrap();
+        internal.name().unwrap().should_eq("internal");
+        assert!(internal.args().next().is_none());
+        assert!(attrs.next().is_none());
+    }
+
     #[test]
     fn function_docs() {
         let e = parse::<Function>("///123\n \n ///abc\n fn main(a b: Int) -> fn(Int) -> Int {}");
@@ -967,6 +1076,37 @@ mod tests {
         assert_eq!(e.op_kind(), Some(UnaryOpKind::Negate));
         e.op_token().unwrap().should_eq("-");
         e.arg().unwrap().syntax().should_eq("1");
+
+        let e = parse::<UnaryOp>("fn a() { -.x }");
+        assert_eq!(e.op_kind(), Some(UnaryOpKind::FloatNegate));
+        e.arg().unwrap().syntax().should_eq("x");
+
+        let e = parse::<UnaryOp>("fn a() { !a && b }");
+        assert_eq!(e.op_kind(), Some(UnaryOpKind::Not));
+        e.arg().unwrap().syntax().should_eq("a");
+    }
+
+    #[test]
+    fn todo_and_panic() {
+        let e = parse::<Todo>("fn a() { todo as \"later\" }");
+        e.message().unwrap().syntax().should_eq("\"later\"");
+        let e = parse::<Panic>("fn a() { panic }");
+        assert!(e.message().is_none());
+    }
+
+    #[test]
+    fn bool_ops() {
+        let e = parse::<BinaryOp>("fn a() { a || b && c != d }");
+        assert_eq!(e.op_kind(), Some(BinaryOpKind::Or));
+        let rhs = e.rhs().unwrap();
+        let Expr::BinaryOp(rhs) = rhs else {
+            panic!("expected a binary op");
+        };
+        assert_eq!(rhs.op_kind(), Some(BinaryOpKind::And));
+        let Expr::BinaryOp(rhs) = rhs.rhs().unwrap() else {
+            panic!("expected a binary op");
+        };
+        assert_eq!(rhs.op_kind(), Some(BinaryOpKind::NotEq));
     }
 
     #[test]
@@ -1044,6 +1184,13 @@ mod tests {
         let mut pats = clause.patterns();
         pats.next().unwrap().syntax().should_eq("b");
         assert!(pats.next().is_none());
+        clause
+            .guard()
+            .unwrap()
+            .expr()
+            .unwrap()
+            .syntax()
+            .should_eq("b == a");
         clause.body().unwrap().syntax().should_eq("1")
     }
 
@@ -1061,7 +1208,50 @@ mod tests {
 
     #[test]
     fn bit_array() {
-        let _b = parse::<BitArray>("fn a() { <<a:size(0)>> <<a:8, rest:bit_array>> }");
+        let b = parse::<BitArray>("fn a() { <<a:size(0)-big, 1:8, rest:bits>> }");
+        let mut segments = b.segments();
+        let first = segments.next().unwrap();
+        first.value().unwrap().syntax().should_eq("a");
+        let mut options = first.options();
+        let size = options.next().unwrap();
+        assert_eq!(size.name().as_deref(), Some("size"));
+        size.arg().unwrap().syntax().should_eq("0");
+        assert_eq!(options.next().unwrap().name().as_deref(), Some("big"));
+        assert!(options.next().is_none());
+
+        let second = segments.next().unwrap();
+        let shorthand = second.options().next().unwrap();
+        assert!(shorthand.name().is_none());
+        shorthand.arg().unwrap().syntax().should_eq("8");
+
+        let third = segments.next().unwrap();
+        third.value().unwrap().syntax().should_eq("rest");
+        assert!(segments.next().is_none());
+    }
+
+    #[test]
+    fn pattern_bit_array() {
+        let b =
+            parse::<PatternBitArray>("fn a(x) { let <<len:8, data:bytes-size(len), _:bits>> = x }");
+        let mut segments = b.segments();
+        segments
+            .next()
+            .unwrap()
+            .pattern()
+            .unwrap()
+            .syntax()
+            .should_eq("len");
+        let data = segments.next().unwrap();
+        data.pattern().unwrap().syntax().should_eq("data");
+        let mut options = data.options();
+        assert_eq!(options.next().unwrap().name().as_deref(), Some("bytes"));
+        options
+            .next()
+            .unwrap()
+            .arg()
+            .unwrap()
+            .syntax()
+            .should_eq("len");
     }
 
     #[test]
@@ -1105,7 +1295,7 @@ mod tests {
     #[test]
     fn constructor_fiels() {
         let e = parse::<VariantRef>("fn pat(a) { let Pat(name_ref: name, age: age) = a \n name }");
-        let mut fields = e.field_list().unwrap().fields().into_iter();
+        let mut fields = e.field_list().unwrap().fields();
 
         fields
             .next()
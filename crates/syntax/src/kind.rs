@@ -288,6 +288,9 @@ def! {
     EXPR_CALL,
     ARG,
     BIT_ARRAY,
+    PATTERN_BIT_ARRAY,
+    BIT_ARRAY_SEGMENT,
+    BIT_ARRAY_OPTION,
     ARG_LIST,
     UNARY_OP,
     BINARY_OP,
@@ -305,6 +308,8 @@ def! {
     FN_BODY,
     LABEL,
     HOLE,
+    TODO,
+    PANIC,
     SOURCE_FILE,
     MODULE_NAME_REF,
     MODULE_CONSTANT,
@@ -352,7 +357,7 @@ def! {
     LIST,
     RECORD,
     EXTERNAL_ATTR,
-    TARGET_ATTR,
+    ATTRIBUTE,
 
     __LAST,
 }
@@ -18,7 +18,7 @@ pub fn lex_string(lex: &mut Lexer<SyntaxKind>) -> bool {
         }
 
         if c == '"' && !escaped {
-            lex.bump(remainder[0..total_len].as_bytes().len());
+            lex.bump(remainder[0..total_len].len());
             return true;
         }
 
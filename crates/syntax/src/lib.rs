@@ -55,6 +55,7 @@ pub enum ErrorKind {
     ExpectedPattern,
     UnexpectedLabel,
     ExpectedBitArray,
+    ExpectedSegmentOption,
 }
 
 impl fmt::Display for ErrorKind {
@@ -77,6 +78,7 @@ impl fmt::Display for ErrorKind {
             Self::ExpectedPattern => "Expected Pattern",
             Self::UnexpectedLabel => "Label not allowed in anonymous function",
             Self::ExpectedBitArray => "Expected bit string expression",
+            Self::ExpectedSegmentOption => "Expected a bit array segment option",
         }
         .fmt(f)
     }
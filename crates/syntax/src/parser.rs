@@ -38,12 +38,16 @@ const PATTERN_FIRST: TokenSet = TokenSet::new(&[
     T!["-"],
     T![".."],
 ]);
+const ATTRIBUTE_ARG_FIRST: TokenSet = TokenSet::new(&[IDENT, STRING, INTEGER, FLOAT]);
 const TYPE_FIRST: TokenSet = TokenSet::new(&[T!["fn"], T!["#"], IDENT, U_IDENT, DISCARD_IDENT]);
+/// Binding power above every infix operator, used for bit array segment values.
+const SEGMENT_VALUE_BP: u8 = 16;
 const EXPR_FIRST: TokenSet = TokenSet::new(&[
     IDENT,
     U_IDENT,
     DISCARD_IDENT,
     T!["-"],
+    T!["-."],
     T!["!"],
     T!["panic"],
     T!["todo"],
@@ -341,9 +345,7 @@ fn attribute(p: &mut Parser) {
     p.expect(T!["@"]);
     match p.nth(0) {
         T!["external"] => external(p, attr),
-        IDENT => {
-            target(p, attr);
-        }
+        IDENT => named_attribute(p, attr),
         _ => {
             p.error(ErrorKind::ExpectedAttribute);
             p.finish_node(attr, ERROR);
@@ -364,13 +366,27 @@ fn external(p: &mut Parser, attr: MarkOpened) {
     p.finish_node(attr, EXTERNAL_ATTR);
 }
 
-fn target(p: &mut Parser, attr: MarkOpened) {
+/// `@target(erlang)`, `@deprecated("message")`, `@internal` and whatever comes next.
+fn named_attribute(p: &mut Parser, attr: MarkOpened) {
     assert!(p.at(IDENT));
     p.expect(IDENT);
-    p.expect(T!["("]);
-    p.expect(IDENT);
-    p.expect(T![")"]);
-    p.finish_node(attr, TARGET_ATTR);
+    if p.eat(T!["("]) {
+        while !p.at(T![")"]) && !p.eof() {
+            if p.at_any(ATTRIBUTE_ARG_FIRST) {
+                p.bump();
+            } else {
+                if p.at_any(STMT_RECOVERY) {
+                    break;
+                }
+                p.bump_with_error(ErrorKind::ExpectedArgument);
+            }
+            if !p.at(T![")"]) {
+                p.expect(T![","]);
+            }
+        }
+        p.expect(T![")"]);
+    }
+    p.finish_node(attr, ATTRIBUTE);
 }
 
 fn custom_type(p: &mut Parser, m: MarkOpened) {
@@ -775,13 +791,14 @@ fn expr_unit(p: &mut Parser) -> Option<MarkClosed> {
         T!["#"] => tuple(p),
         T!["["] => list(p),
         T!["case"] => case(p),
-        T!["panic"] | T!["todo"] => {
+        kind @ (T!["panic"] | T!["todo"]) => {
             let m = p.start_node();
             p.bump();
             if p.eat(T!["as"]) {
                 expr(p);
             }
-            p.finish_node(m, MISSING)
+            let node = if kind == T!["todo"] { TODO } else { PANIC };
+            p.finish_node(m, node)
         }
         T!["fn"] => {
             let m = p.start_node();
@@ -800,21 +817,88 @@ fn expr_unit(p: &mut Parser) -> Option<MarkClosed> {
     Some(res)
 }
 
-// ToDo: Parse bit string correctly
 fn bit_array(p: &mut Parser<'_>) -> MarkClosed {
     assert!(p.at(T!["<<"]));
-    p.expect(T!["<<"]);
     let m = p.start_node();
+    p.expect(T!["<<"]);
     while !p.at(T![">>"]) && !p.eof() {
-        p.bump();
-        if p.at_any(STMT_RECOVERY) {
-            break;
+        if p.at_any(EXPR_FIRST) {
+            let s = p.start_node();
+            // Segment values bind tighter than any infix operator, so `-` is left for the options.
+            expr_bp(p, SEGMENT_VALUE_BP);
+            bit_array_options(p);
+            p.finish_node(s, BIT_ARRAY_SEGMENT);
+            if !p.at(T![">>"]) {
+                p.expect(T![","]);
+            }
+        } else {
+            if p.at_any(STMT_EXPR_RECOVERY) {
+                break;
+            }
+            p.bump_with_error(ErrorKind::ExpectedExpression);
         }
     }
     p.expect(T![">>"]);
     p.finish_node(m, BIT_ARRAY)
 }
 
+fn pattern_bit_array(p: &mut Parser<'_>) -> MarkClosed {
+    assert!(p.at(T!["<<"]));
+    let m = p.start_node();
+    p.expect(T!["<<"]);
+    while !p.at(T![">>"]) && !p.eof() {
+        if p.at_any(PATTERN_FIRST) {
+            let s = p.start_node();
+            pattern(p);
+            bit_array_options(p);
+            p.finish_node(s, BIT_ARRAY_SEGMENT);
+            if !p.at(T![">>"]) {
+                p.expect(T![","]);
+            }
+        } else {
+            if p.at_any(PATTERN_RECOVERY) {
+                break;
+            }
+            p.bump_with_error(ErrorKind::ExpectedPattern);
+        }
+    }
+    p.expect(T![">>"]);
+    p.finish_node(m, PATTERN_BIT_ARRAY)
+}
+
+// e.g. `:size(8)-unit(1)-big-signed` or the `:8` size shorthand
+fn bit_array_options(p: &mut Parser<'_>) {
+    if !p.eat(T![":"]) {
+        return;
+    }
+    loop {
+        let m = p.start_node();
+        match p.nth(0) {
+            IDENT => {
+                p.bump();
+                if p.eat(T!["("]) {
+                    if p.at_any(EXPR_FIRST) {
+                        expr(p);
+                    } else {
+                        p.error(ErrorKind::ExpectedExpression);
+                    }
+                    p.expect(T![")"]);
+                }
+            }
+            INTEGER => {
+                let lit = p.start_node();
+                p.bump();
+                p.finish_node(lit, LITERAL);
+            }
+            _ => p.error(ErrorKind::ExpectedSegmentOption),
+        }
+        p.finish_node(m, BIT_ARRAY_OPTION);
+        if !p.eat(T!["-"]) {
+            break;
+        }
+    }
+}
+
 fn case(p: &mut Parser) -> MarkClosed {
     assert!(p.at(T!["case"]));
     let m = p.start_node();
@@ -960,7 +1044,7 @@ fn pattern(p: &mut Parser) {
             }
             literal
         }
-        T!["<<"] => bit_array(p),
+        T!["<<"] => pattern_bit_array(p),
         T!["["] => pattern_list(p),
         T!["-"] | T!["!"] => {
             let u = p.start_node();
@@ -1447,7 +1531,7 @@ impl SyntaxKind {
     fn prefix_bp(self) -> Option<u8> {
         Some(match self {
             T!["!"] => 17,
-            T!["-"] => 18,
+            T!["-"] | T!["-."] => 18,
             _ => return None,
         })
     }
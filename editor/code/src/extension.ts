@@ -18,6 +18,13 @@ export const syntaxTree = new lc.RequestType<SyntaxTreeParams, string, void>(
   "glas/syntaxTree"
 );
 
+export type Todo = {
+  location: lc.Location;
+  message: string | null;
+};
+
+export const todos = new lc.RequestType0<Todo[], void>("glas/todos");
+
 export function activate(context: vscode.ExtensionContext) {
   if (vscode.extensions.getExtension("gleam.gleam")) {
         vscode.window
@@ -88,6 +95,22 @@ export function activate(context: vscode.ExtensionContext) {
       preview: false
     });
   }));
+  context.subscriptions.push(vscode.commands.registerCommand('glas.todos', async () => {
+    const items = (await client.sendRequest(todos)).map((todo) => {
+      const uri = vscode.Uri.parse(todo.location.uri);
+      const line = todo.location.range.start.line;
+      return {
+        label: todo.message ?? "todo",
+        description: `${vscode.workspace.asRelativePath(uri)}:${line + 1}`,
+        todo,
+      };
+    });
+    const picked = await vscode.window.showQuickPick(items, { placeHolder: "Go to todo" });
+    if (picked) {
+      const location = client.protocol2CodeConverter.asLocation(picked.todo.location);
+      await vscode.window.showTextDocument(location.uri, { selection: location.range });
+    }
+  }));
 
 }
 